@@ -0,0 +1,139 @@
+use crate::runner::Part;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The default directory from which recorded answers are read
+pub const ANSWERS_DIR: &str = "answers";
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Integer(i64),
+    String(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// The recorded answers for a day, read from a `day_<n>.toml` file such as
+///
+/// ```toml
+/// part_1 = 24000
+/// part_2 = "CMZ"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    part_1: Option<Value>,
+    part_2: Option<Value>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    /// Loads the recorded answers for a day, or `None` if none have been recorded
+    pub fn load(dir: &Path, day: u8) -> Result<Option<Self>, AnswersError> {
+        Self::read(&dir.join(format!("day_{}.toml", day)))
+    }
+
+    /// Reads answers from a file, or `None` if the file does not exist
+    pub fn read(path: &Path) -> Result<Option<Self>, AnswersError> {
+        let path = path.to_path_buf();
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(AnswersError::Io { path, source }),
+        };
+        Self::parse(&input)
+            .map(Some)
+            .map_err(|source| AnswersError::Toml { path, source })
+    }
+
+    pub fn expected(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.part_1.as_ref(),
+            Part::Two => self.part_2.as_ref(),
+        }
+        .map(Value::to_string)
+    }
+
+    /// Checks an answer against the recorded answer, ignoring trailing whitespace
+    pub fn verify(&self, part: Part, answer: &str) -> Verdict {
+        match self.expected(part) {
+            Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Toml {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "could not read answers {}: {}", path.display(), source)
+            }
+            AnswersError::Toml { path, source } => {
+                write!(f, "invalid answers {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Toml { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answers, Verdict};
+    use crate::runner::Part;
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::parse("part_1 = 24000").unwrap();
+        assert_eq!(Verdict::Pass, answers.verify(Part::One, "24000"));
+        assert_eq!(
+            Verdict::Fail {
+                expected: "24000".into()
+            },
+            answers.verify(Part::One, "45000")
+        );
+        assert_eq!(Verdict::Missing, answers.verify(Part::Two, "45000"));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::parse::ParseError;
+use crate::Solution;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Timings of each phase of a day, one per iteration
+#[derive(Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub part_1: Vec<Duration>,
+    pub part_2: Vec<Duration>,
+}
+
+pub(crate) fn samples<S: Solution>(input: &str, iterations: usize) -> Result<Samples, ParseError> {
+    let mut samples = Samples::default();
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let solution = black_box(S::parse(black_box(input))?);
+        samples.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(solution.part_1());
+        samples.part_1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(solution.part_2());
+        samples.part_2.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+/// Summary statistics of a phase's timings, in nanoseconds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        nanos.sort_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let median = match nanos.len() {
+            0 => f64::NAN,
+            len if len % 2 == 0 => (nanos[len / 2 - 1] + nanos[len / 2]) / 2.0,
+            len => nanos[len / 2],
+        };
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        Self {
+            mean,
+            median,
+            stddev: variance.sqrt(),
+            min: nanos.first().copied().unwrap_or(f64::NAN),
+            max: nanos.last().copied().unwrap_or(f64::NAN),
+        }
+    }
+}
+
+/// The benchmark results of a day
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayResults {
+    pub day: u8,
+    pub title: String,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl DayResults {
+    pub fn new(day: u8, title: &str, samples: &Samples) -> Self {
+        Self {
+            day,
+            title: title.into(),
+            iterations: samples.parse.len(),
+            parse: Stats::new(&samples.parse),
+            part_1: Stats::new(&samples.part_1),
+            part_2: Stats::new(&samples.part_2),
+        }
+    }
+}
+
+/// The benchmark results of a set of days, written as JSON for comparing between commits
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Results {
+    pub days: Vec<DayResults>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stats() {
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_nanos);
+        let stats = Stats::new(&samples);
+        assert_eq!(5.0, stats.mean);
+        assert_eq!(4.5, stats.median);
+        assert_eq!(2.0, stats.stddev);
+        assert_eq!(2.0, stats.min);
+        assert_eq!(9.0, stats.max);
+    }
+}
@@ -0,0 +1,691 @@
+use advent_of_code_2022::answers::{self, Answers, Verdict};
+use advent_of_code_2022::bench::{DayResults, Results};
+use advent_of_code_2022::day_10::assembler;
+use advent_of_code_2022::day_10::cpu::InstructionSet;
+use advent_of_code_2022::day_10::debugger::{self, Debugger};
+use advent_of_code_2022::day_10::image;
+use advent_of_code_2022::day_10::trace::{self, Format};
+use advent_of_code_2022::day_10::{DisplayConfig, Program};
+use advent_of_code_2022::day_7::shell::Shell;
+use advent_of_code_2022::day_7::transcript;
+use advent_of_code_2022::day_7::Filesystem;
+use advent_of_code_2022::input::Source;
+use advent_of_code_2022::runner::{self, Day, Part};
+use clap::{Args, Parser, Subcommand};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day, or all days, and print the answers
+    Run {
+        /// The day to run, or `all`
+        day: Selection,
+        /// Only run the given part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Run every day and check the answers against the recorded answers
+    Verify {
+        /// Read inputs from `day_<n>.txt` files in a directory
+        #[arg(long, env = "AOC_INPUTS_DIR")]
+        inputs_dir: Option<PathBuf>,
+        /// Read recorded answers from `day_<n>.toml` files in a directory
+        #[arg(long, default_value = answers::ANSWERS_DIR)]
+        answers_dir: PathBuf,
+    },
+    /// Benchmark parsing and solving each part of a day, or all days
+    Bench {
+        /// The day to benchmark, or `all`
+        day: Selection,
+        /// The number of times to parse and solve each day
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Write the results as JSON to a file
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Read inputs from `day_<n>.txt` files in a directory
+        #[arg(long, env = "AOC_INPUTS_DIR")]
+        inputs_dir: Option<PathBuf>,
+    },
+    /// Step through a day 10 program, reading debugger commands from stdin
+    Debug {
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Trace every cycle of a day 10 program as CSV or JSON lines
+    Trace {
+        /// `csv` or `jsonl`
+        #[arg(long, default_value = "csv")]
+        format: Format,
+        /// Write the trace to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Stop after this many cycles, by default once the screen has been drawn
+        #[arg(long)]
+        max_cycles: Option<usize>,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Compile a 40x6 screen of `#` and `.` into a day 10 program that draws it
+    Assemble {
+        /// The screen to draw, or `-` for stdin
+        screen: PathBuf,
+    },
+    /// Render the screen drawn by a day 10 program as an image
+    Render {
+        /// The image to write
+        output: PathBuf,
+        /// `pbm`, `ppm` or `png`, by default from the output's extension, or else `png`
+        #[arg(long)]
+        format: Option<image::Format>,
+        /// The width and height of each pixel of the screen in the image
+        #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+        /// Also write an animated PNG with a frame for every cycle, showing the beam drawing
+        /// the screen
+        #[arg(long)]
+        animation: Option<PathBuf>,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Explore a day 7 filesystem with shell commands read from stdin
+    Shell {
+        /// Start with an empty filesystem instead of replaying a transcript
+        #[arg(long, conflicts_with = "input")]
+        empty: bool,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Show the day 7 filesystem as an indented tree with the size of every entry
+    Tree {
+        /// Stop this many directories below the root
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show sizes in powers of 1024, like `1.5K` or `47M`
+        #[arg(short = 'H', long)]
+        human_readable: bool,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Show the size of every day 7 directory, largest first
+    Du {
+        /// Stop this many directories below the root
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show sizes in powers of 1024, like `1.5K` or `47M`
+        #[arg(short = 'H', long)]
+        human_readable: bool,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Write the day 7 filesystem as JSON, with every entry, its parent and its size
+    Json {
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Report conflicts in the day 7 transcript, like a file listed twice with different sizes
+    Check {
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Print a day 7 terminal transcript that rebuilds a directory on disk
+    Transcript {
+        /// The directory to walk
+        dir: PathBuf,
+    },
+    /// List the registered days
+    List,
+}
+
+/// Where to read a day's input from
+#[derive(Args)]
+struct InputArgs {
+    /// Read the input from a file instead of the inputs directory, or `-` for stdin
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Read inputs from `day_<n>.txt` files in a directory
+    #[arg(long, env = "AOC_INPUTS_DIR")]
+    inputs_dir: Option<PathBuf>,
+}
+
+impl InputArgs {
+    /// The source to read from, where `--input` wins over `--inputs-dir`
+    fn source(&self) -> Source {
+        match (&self.input, &self.inputs_dir) {
+            (Some(path), _) if path.as_os_str() == "-" => Source::Stdin,
+            (Some(path), _) => Source::Path(path.clone()),
+            (None, Some(dir)) => Source::Dir(dir.clone()),
+            (None, None) => Source::default(),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            Ok(Selection::All)
+        } else {
+            s.parse()
+                .map(Selection::Day)
+                .map_err(|_| format!("expected a day number or `all`, found `{}`", s))
+        }
+    }
+}
+
+impl Selection {
+    fn days(&self) -> Result<Vec<&'static Day>, String> {
+        match self {
+            Selection::All => Ok(runner::DAYS.iter().collect()),
+            Selection::Day(d) => runner::day(*d)
+                .map(|day| vec![day])
+                .ok_or_else(|| format!("day {} is not implemented", d)),
+        }
+    }
+}
+
+fn run(days: &[&Day], parts: &[Part], source: &Source) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    println!(
+        "{:>3}  {:<24}  {:>4}  {:<40}  {:>10}  {:>10}",
+        "day", "title", "part", "answer", "parse", "solve"
+    );
+    for day in days {
+        let input = match source.load(day.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day {}: {}", day.day, e);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let run = match day.run(&input, parts) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("day {}: {}", day.day, e);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        for answer in &run.answers {
+            let mut lines = answer.value.lines();
+            println!(
+                "{:>3}  {:<24}  {:>4}  {:<40}  {:>10}  {:>10}",
+                day.day,
+                day.title,
+                answer.part.number(),
+                lines.next().unwrap_or_default(),
+                format!("{:.2?}", run.parse),
+                format!("{:.2?}", answer.elapsed),
+            );
+            for line in lines {
+                println!("{:>37}{}", "", line);
+            }
+        }
+    }
+    exit_code
+}
+
+fn verify(source: &Source, answers_dir: &Path) -> ExitCode {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    println!("{:>3}  {:<24}  {:>4}  status", "day", "title", "part");
+    for day in runner::DAYS {
+        let run = source
+            .load(day.day)
+            .map_err(|e| e.to_string())
+            .and_then(|input| day.run(&input, &Part::ALL).map_err(|e| e.to_string()));
+        let answers = Answers::load(answers_dir, day.day).map_err(|e| e.to_string());
+        let (run, answers) = match (run, answers) {
+            (Ok(run), Ok(answers)) => (run, answers.unwrap_or_default()),
+            (Err(e), _) | (_, Err(e)) => {
+                println!("{:>3}  {:<24}  {:>4}  error: {}", day.day, day.title, "", e);
+                failed += Part::ALL.len();
+                continue;
+            }
+        };
+        for answer in &run.answers {
+            let status = match answers.verify(answer.part, &answer.value) {
+                Verdict::Pass => {
+                    passed += 1;
+                    "pass".to_string()
+                }
+                Verdict::Fail { expected } => {
+                    failed += 1;
+                    format!("FAIL: expected {:?}, found {:?}", expected, answer.value)
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    format!("missing: found {:?}", answer.value)
+                }
+            };
+            println!(
+                "{:>3}  {:<24}  {:>4}  {}",
+                day.day,
+                day.title,
+                answer.part.number(),
+                status
+            );
+        }
+    }
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench(days: &[&Day], iterations: usize, source: &Source, output: Option<&Path>) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut results = Results::default();
+    println!(
+        "{:>3}  {:<24}  {:<6}  {:>10}  {:>10}  {:>10}",
+        "day", "title", "phase", "mean", "median", "stddev"
+    );
+    for day in days {
+        let samples = match source
+            .load(day.day)
+            .map_err(|e| e.to_string())
+            .and_then(|input| day.bench(&input, iterations).map_err(|e| e.to_string()))
+        {
+            Ok(samples) => samples,
+            Err(e) => {
+                eprintln!("day {}: {}", day.day, e);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let day_results = DayResults::new(day.day, day.title, &samples);
+        for (phase, stats) in [
+            ("parse", &day_results.parse),
+            ("part 1", &day_results.part_1),
+            ("part 2", &day_results.part_2),
+        ] {
+            println!(
+                "{:>3}  {:<24}  {:<6}  {:>10}  {:>10}  {:>10}",
+                day.day,
+                day.title,
+                phase,
+                nanos(stats.mean),
+                nanos(stats.median),
+                nanos(stats.stddev),
+            );
+        }
+        results.days.push(day_results);
+    }
+
+    if let Some(output) = output {
+        let json = serde_json::to_string_pretty(&results).unwrap();
+        if let Err(e) = fs::write(output, json + "\n") {
+            eprintln!("could not write results {}: {}", output.display(), e);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+    exit_code
+}
+
+/// Loads a day 10 program, which may use the extended instruction set
+fn program(source: &Source) -> Result<Program, String> {
+    let input = source.load(10).map_err(|e| e.to_string())?;
+    InstructionSet::extended()
+        .parse_program(&input)
+        .map(|instructions| Program::new(instructions, DisplayConfig::default()))
+        .map_err(|e| format!("day 10: {}", e))
+}
+
+fn debug(source: &Source) -> ExitCode {
+    let mut debugger = match program(source) {
+        Ok(program) => Debugger::new(program),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{}", debugger.state());
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("(debug) ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or_default() == 0 {
+            break;
+        }
+        match line.parse() {
+            Ok(debugger::Command::Quit) => break,
+            Ok(command) => println!("{}", debugger.execute(command)),
+            Err(e) => println!("{}", e),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn trace(
+    source: &Source,
+    format: Format,
+    output: Option<&Path>,
+    max_cycles: Option<usize>,
+) -> ExitCode {
+    let mut program = match program(source) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = program.config();
+    let max_cycles = max_cycles.unwrap_or(config.width * config.height);
+
+    let writer: Box<dyn Write> = match output {
+        Some(path) => match fs::File::create(path) {
+            Ok(file) => Box::new(io::BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("could not write {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    };
+    let result = trace::Writer::new(format, writer).and_then(|mut writer| {
+        for _ in 0..max_cycles {
+            match program.step() {
+                Some(step) => writer.write(&step)?,
+                None => break,
+            }
+        }
+        writer.flush()
+    });
+    if let Err(e) = result {
+        eprintln!("could not write trace: {}", e);
+        return ExitCode::FAILURE;
+    }
+    if !program.cpu().is_halted() {
+        eprintln!(
+            "stopped after {} cycles before the program halted",
+            max_cycles
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn assemble(path: &Path) -> ExitCode {
+    let screen = if path.as_os_str() == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(path)
+    };
+    let instructions = screen
+        .map_err(|e| format!("could not read screen {}: {}", path.display(), e))
+        .and_then(|screen| {
+            assembler::assemble(&screen, &DisplayConfig::default()).map_err(|e| e.to_string())
+        });
+    match instructions {
+        Ok(instructions) => {
+            for instruction in instructions {
+                println!("{}", instruction);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn render(
+    source: &Source,
+    output: &Path,
+    format: image::Format,
+    scale: usize,
+    animation: Option<&Path>,
+) -> ExitCode {
+    let mut program = match program(source) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if animation.is_some() {
+        program.enable_trace();
+    }
+    // The screen is full after this many cycles, so a program that loops forever still stops
+    let config = *program.config();
+    for _ in 0..config.width * config.height {
+        if !program.tick() {
+            break;
+        }
+    }
+
+    let create = |path: &Path| {
+        fs::File::create(path)
+            .map(io::BufWriter::new)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))
+    };
+    let result = create(output)
+        .and_then(|file| {
+            image::write(&image::screen(&program), format, scale, file)
+                .map_err(|e| format!("could not write {}: {}", output.display(), e))
+        })
+        .and_then(|()| {
+            let Some(path) = animation else {
+                return Ok(());
+            };
+            let trace = program.trace().unwrap_or_default();
+            let frames = image::frames(trace, &config).collect::<Vec<_>>();
+            create(path).and_then(|file| {
+                image::write_animation(&frames, scale, 50, file)
+                    .map_err(|e| format!("could not write {}: {}", path.display(), e))
+            })
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn shell(source: Option<&Source>) -> ExitCode {
+    let mut shell = Shell::new(Filesystem::new());
+    if let Some(source) = source {
+        let replayed = source
+            .load(7)
+            .map_err(|e| e.to_string())
+            .and_then(|input| shell.replay(&input).map_err(|e| format!("day 7: {}", e)));
+        if let Err(e) = replayed {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("{} $ ", shell.filesystem().pwd());
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or_default() == 0 {
+            break;
+        }
+        match line.trim() {
+            "" => continue,
+            "exit" => break,
+            command => match shell.run(command) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => println!("{}", output),
+                Err(e) => println!("{}", e),
+            },
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn check(source: &Source) -> ExitCode {
+    match source
+        .load(7)
+        .map_err(|e| e.to_string())
+        .and_then(|input| Filesystem::validate(&input).map_err(|e| format!("day 7: {}", e)))
+    {
+        Ok((_, warnings)) if warnings.is_empty() => ExitCode::SUCCESS,
+        Ok((_, warnings)) => {
+            for warning in warnings {
+                println!("{}", warning);
+            }
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn report(source: &Source, report: impl FnOnce(&Filesystem) -> String) -> ExitCode {
+    match source
+        .load(7)
+        .map_err(|e| e.to_string())
+        .and_then(|input| Filesystem::init(&input).map_err(|e| format!("day 7: {}", e)))
+    {
+        Ok(fs) => {
+            print!("{}", report(&fs));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos as u64))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, inputs } => {
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(_) => vec![Part::Two],
+                None => Part::ALL.to_vec(),
+            };
+            let days = match day.days() {
+                Ok(days) => days,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if inputs.input.is_some() && days.len() > 1 {
+                eprintln!("--input can only be used when running a single day");
+                return ExitCode::FAILURE;
+            }
+            run(&days, &parts, &inputs.source())
+        }
+        Command::Verify {
+            inputs_dir,
+            answers_dir,
+        } => {
+            let source = inputs_dir.map(Source::Dir).unwrap_or_default();
+            verify(&source, &answers_dir)
+        }
+        Command::Bench {
+            day,
+            iterations,
+            output,
+            inputs_dir,
+        } => {
+            let days = match day.days() {
+                Ok(days) => days,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let source = inputs_dir.map(Source::Dir).unwrap_or_default();
+            bench(&days, iterations as usize, &source, output.as_deref())
+        }
+        Command::Debug { inputs } => debug(&inputs.source()),
+        Command::Trace {
+            format,
+            output,
+            max_cycles,
+            inputs,
+        } => trace(&inputs.source(), format, output.as_deref(), max_cycles),
+        Command::Assemble { screen } => assemble(&screen),
+        Command::Render {
+            output,
+            format,
+            scale,
+            animation,
+            inputs,
+        } => render(
+            &inputs.source(),
+            &output,
+            format
+                .or_else(|| image::Format::from_path(&output))
+                .unwrap_or(image::Format::Png),
+            scale as usize,
+            animation.as_deref(),
+        ),
+        Command::Shell { empty, inputs } => shell((!empty).then(|| inputs.source()).as_ref()),
+        Command::Tree {
+            depth,
+            human_readable,
+            inputs,
+        } => report(&inputs.source(), |fs| fs.tree(depth, human_readable)),
+        Command::Du {
+            depth,
+            human_readable,
+            inputs,
+        } => report(&inputs.source(), |fs| fs.du(depth, human_readable)),
+        Command::Json { inputs } => report(&inputs.source(), |fs| {
+            format!("{}\n", serde_json::to_string_pretty(fs).unwrap())
+        }),
+        Command::Check { inputs } => check(&inputs.source()),
+        Command::Transcript { dir } => match transcript::from_dir(&dir) {
+            Ok(fs) => {
+                print!("{}", transcript::transcript(&fs));
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("could not read {}: {}", dir.display(), e);
+                ExitCode::FAILURE
+            }
+        },
+        Command::List => {
+            for day in runner::DAYS {
+                println!("{:>3}  {}", day.day, day.title);
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -1,14 +0,0 @@
-const INPUT: &str = include_str!("../../input/day_11.txt");
-
-fn main() {}
-
-#[cfg(test)]
-mod test {
-    const TEST_INPUT: &str = r#""#;
-
-    #[test]
-    fn test_part_1() {}
-
-    #[test]
-    fn test_part_2() {}
-}
@@ -0,0 +1,104 @@
+use crate::parse::{self, ParseError};
+use crate::Solution;
+use std::cmp;
+
+pub struct Inventory {
+    items: Vec<usize>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self { items: vec![] }
+    }
+
+    pub fn sum(&self) -> usize {
+        self.items.iter().sum()
+    }
+
+    pub fn push(&mut self, item: usize) {
+        self.items.push(item)
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Inventories(Vec<Inventory>);
+
+impl Inventories {
+    fn sorted_sums(&self) -> Vec<usize> {
+        let mut sums = self.0.iter().map(Inventory::sum).collect::<Vec<_>>();
+        sums.sort_by_key(|y| cmp::Reverse(*y));
+        sums
+    }
+}
+
+impl Solution for Inventories {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    /// Parses each elf's items, with a blank line between elves. There must be at least three
+    /// elves to find the top three.
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut inventories = vec![];
+        let mut inventory = Inventory::new();
+        let mut last = 0;
+        for line in parse::lines(input) {
+            last = line.number;
+            if line.text.is_empty() {
+                if !inventory.items.is_empty() {
+                    inventories.push(std::mem::take(&mut inventory));
+                }
+                continue;
+            }
+            inventory.push(line.parse(line.text)?);
+        }
+        if !inventory.items.is_empty() {
+            inventories.push(inventory);
+        }
+
+        if inventories.len() < 3 {
+            return Err(ParseError::new(
+                last + 1,
+                1,
+                "",
+                format!("expected at least 3 elves, found {}", inventories.len()),
+            ));
+        }
+        Ok(Self(inventories))
+    }
+
+    fn part_1(&self) -> usize {
+        self.sorted_sums().first().copied().unwrap_or_default()
+    }
+
+    fn part_2(&self) -> usize {
+        self.sorted_sums().iter().take(3).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Inventories;
+    use crate::examples::examples;
+    use crate::parse::ParseError;
+    use crate::Solution;
+
+    examples!(Inventories, 1);
+
+    #[test]
+    fn test_too_few_elves() {
+        assert_eq!(
+            Err(ParseError::new(
+                4,
+                1,
+                "",
+                "expected at least 3 elves, found 2"
+            )),
+            Inventories::parse("1000\n\n2000").map(|_| ())
+        );
+    }
+}
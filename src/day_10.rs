@@ -0,0 +1,319 @@
+pub mod assembler;
+pub mod cpu;
+pub mod debugger;
+pub mod image;
+pub mod trace;
+
+use crate::ocr;
+use crate::parse::ParseError;
+use crate::Solution;
+use cpu::{Cpu, Cycle, Instruction, InstructionSet, Observer};
+use std::cell::{RefCell, RefMut};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+use trace::Step;
+
+/// When the signal strength is sampled: during the `first` cycle and every `interval` cycles after
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sampling {
+    pub first: usize,
+    pub interval: usize,
+}
+
+impl Sampling {
+    pub fn is_sampled(&self, cycle: usize) -> bool {
+        cycle >= self.first && (cycle - self.first).is_multiple_of(self.interval)
+    }
+}
+
+/// The geometry of the CRT and the sprite it draws, and when the signal strength is sampled
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DisplayConfig {
+    pub width: usize,
+    pub height: usize,
+    pub sprite_width: usize,
+    pub sampling: Sampling,
+}
+
+impl DisplayConfig {
+    /// The columns the sprite covers when `x` is at its middle, or left of the middle if it
+    /// is an even number of pixels wide
+    pub fn sprite(&self, x: i32) -> RangeInclusive<i32> {
+        let left = x.saturating_sub((self.sprite_width as i32 - 1) / 2);
+        left..=left.saturating_add(self.sprite_width as i32 - 1)
+    }
+
+    /// Whether the pixel drawn during a cycle is lit
+    pub fn is_lit(&self, cycle: usize, x: i32) -> bool {
+        let column = (cycle - 1) % self.width;
+        self.sprite(x).contains(&(column as i32))
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            width: 40,
+            height: 6,
+            sprite_width: 3,
+            sampling: Sampling {
+                first: 20,
+                interval: 40,
+            },
+        }
+    }
+}
+
+/// Records the signal strength during each sampled cycle
+pub struct SignalStrength {
+    sampling: Sampling,
+    samples: Vec<i32>,
+}
+
+impl SignalStrength {
+    pub fn new(sampling: Sampling) -> Self {
+        Self {
+            sampling,
+            samples: vec![],
+        }
+    }
+
+    pub fn sum(&self) -> i32 {
+        self.samples.iter().fold(0, |sum, s| sum.wrapping_add(*s))
+    }
+}
+
+impl Observer for SignalStrength {
+    fn on_cycle(&mut self, cycle: &Cycle) {
+        if self.sampling.is_sampled(cycle.number) {
+            self.samples
+                .push((cycle.number as i32).wrapping_mul(cycle.registers.x()));
+        }
+    }
+}
+
+/// Draws a pixel each cycle, lit if the sprite around `x` covers it, until the screen is full
+pub struct Crt {
+    config: DisplayConfig,
+    rows: Vec<Vec<char>>,
+    drew: bool,
+}
+
+impl Crt {
+    pub fn new(config: DisplayConfig) -> Self {
+        Self {
+            config,
+            rows: vec![],
+            drew: false,
+        }
+    }
+
+    pub fn screen(&self) -> String {
+        let mut screen = String::new();
+        for row in &self.rows {
+            writeln!(&mut screen, "{}", String::from_iter(row)).unwrap();
+        }
+        screen
+    }
+
+    /// The row the beam is drawing, or last drew
+    pub fn row(&self) -> String {
+        self.rows.last().map(String::from_iter).unwrap_or_default()
+    }
+
+    /// The pixel the beam drew during the last cycle, or None if the screen was already full
+    pub fn pixel(&self) -> Option<char> {
+        self.drew
+            .then(|| self.rows.last().and_then(|row| row.last()).copied())
+            .flatten()
+    }
+}
+
+impl Observer for Crt {
+    fn on_cycle(&mut self, cycle: &Cycle) {
+        let DisplayConfig { width, height, .. } = self.config;
+        self.drew = cycle.number <= width * height;
+        if !self.drew {
+            return;
+        }
+        if (cycle.number - 1).is_multiple_of(width) {
+            self.rows.push(Vec::with_capacity(width));
+        }
+        let lit = self.config.is_lit(cycle.number, cycle.registers.x());
+        self.rows
+            .last_mut()
+            .unwrap()
+            .push(if lit { '#' } else { '.' });
+    }
+}
+
+pub struct Program {
+    cpu: Cpu,
+    signal: SignalStrength,
+    crt: Crt,
+    trace: Option<Vec<Step>>,
+}
+
+impl Program {
+    pub fn new(instructions: Vec<Box<dyn Instruction>>, config: DisplayConfig) -> Self {
+        Self {
+            cpu: Cpu::new(instructions),
+            signal: SignalStrength::new(config.sampling),
+            crt: Crt::new(config),
+            trace: None,
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    pub fn config(&self) -> &DisplayConfig {
+        &self.crt.config
+    }
+
+    /// Records a trace of every cycle run from now on
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    /// The trace of every cycle run since it was enabled
+    pub fn trace(&self) -> Option<&[Step]> {
+        self.trace.as_deref()
+    }
+
+    /// Runs a single cycle, returning false if the program has halted
+    pub fn tick(&mut self) -> bool {
+        self.step().is_some()
+    }
+
+    /// Runs a single cycle, returning what happened during it, or None if the program has halted
+    pub fn step(&mut self) -> Option<Step> {
+        let instruction = self.cpu.current().map(|(i, _)| i.to_string());
+        let x_before = self.cpu.registers().x();
+        if !self
+            .cpu
+            .tick(&mut [&mut self.signal as &mut dyn Observer, &mut self.crt][..])
+        {
+            return None;
+        }
+
+        let step = Step {
+            cycle: self.cpu.cycle(),
+            instruction: instruction.unwrap_or_default(),
+            x_before,
+            x_after: self.cpu.registers().x(),
+            sprite: x_before,
+            pixel: self.crt.pixel(),
+        };
+        if let Some(trace) = &mut self.trace {
+            trace.push(step.clone());
+        }
+        Some(step)
+    }
+
+    /// Runs the program until it halts
+    pub fn execute(&mut self) {
+        while self.tick() {}
+    }
+
+    pub fn sum_signal_strength(&self) -> i32 {
+        self.signal.sum()
+    }
+
+    pub fn screen(&self) -> String {
+        self.crt.screen()
+    }
+
+    /// The row of the screen the beam is drawing, or last drew
+    pub fn row(&self) -> String {
+        self.crt.row()
+    }
+}
+
+/// The puzzle's program, run to the end by whichever part first needs it, so that parsing
+/// only parses
+pub struct Device(RefCell<Program>);
+
+impl Device {
+    /// The program, once it has halted
+    pub fn run(&self) -> RefMut<'_, Program> {
+        let mut program = self.0.borrow_mut();
+        program.execute();
+        program
+    }
+}
+
+impl Solution for Device {
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let program = Program::new(
+            InstructionSet::default().parse_program(input)?,
+            DisplayConfig::default(),
+        );
+        Ok(Self(RefCell::new(program)))
+    }
+
+    fn part_1(&self) -> i32 {
+        self.run().sum_signal_strength()
+    }
+
+    /// The letters drawn on the screen, or the screen itself if they cannot be recognized
+    fn part_2(&self) -> String {
+        let screen = self.run().screen();
+        ocr::recognize(&screen).unwrap_or(screen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::cpu::InstructionSet;
+    use super::{Device, DisplayConfig, Program, Sampling};
+    use crate::examples::examples;
+
+    examples!(Device, 10);
+
+    #[test]
+    fn test_extended_program() {
+        let instructions = InstructionSet::extended()
+            .parse_program("addy 5\naddx 2\nmul x 2\njmp -1")
+            .unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        for _ in 0..20 {
+            program.tick();
+        }
+        assert_eq!(20 * 48, program.sum_signal_strength());
+        assert_eq!("###.#..", &program.screen()[..7]);
+
+        let instructions = InstructionSet::extended()
+            .parse_program("addx 2147483647\naddx 1\nmul x 2\njmp -1")
+            .unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        for _ in 0..1000 {
+            program.tick();
+        }
+        assert_eq!(0, program.cpu().registers().x());
+    }
+
+    #[test]
+    fn test_display_config() {
+        let instructions = InstructionSet::default()
+            .parse_program(&"addx 1\n".repeat(7))
+            .unwrap();
+        let config = DisplayConfig {
+            width: 4,
+            height: 3,
+            sprite_width: 2,
+            sampling: Sampling {
+                first: 2,
+                interval: 5,
+            },
+        };
+        let mut program = Program::new(instructions, config);
+        program.execute();
+        assert_eq!(2 + 7 * 4 + 12 * 6, program.sum_signal_strength());
+        assert_eq!(".###\n....\n....\n", program.screen());
+    }
+}
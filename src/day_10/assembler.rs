@@ -0,0 +1,188 @@
+use super::cpu::{Add, Instruction, Noop};
+use super::DisplayConfig;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum AssembleError {
+    Parse(ParseError),
+    Size {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// No program can draw the pixel at this position along with those before it
+    Impossible {
+        row: usize,
+        column: usize,
+    },
+}
+
+impl Display for AssembleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssembleError::Parse(e) => write!(f, "invalid screen: {}", e),
+            AssembleError::Size { expected, found } => write!(
+                f,
+                "expected a {}x{} screen but found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            AssembleError::Impossible { row, column } => write!(
+                f,
+                "cannot draw row {}, column {} with the sprite",
+                row + 1,
+                column + 1
+            ),
+        }
+    }
+}
+
+impl Error for AssembleError {}
+
+/// Compiles a screen of `#` and `.` pixels into a program of `addx` and `noop` that draws it
+pub fn assemble(
+    screen: &str,
+    config: &DisplayConfig,
+) -> Result<Vec<Box<dyn Instruction>>, AssembleError> {
+    let pixels = Grid::parse(screen.trim_end(), |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .map_err(AssembleError::Parse)?;
+    if (pixels.width(), pixels.height()) != (config.width, config.height) {
+        return Err(AssembleError::Size {
+            expected: (config.width, config.height),
+            found: (pixels.width(), pixels.height()),
+        });
+    }
+
+    let pixels = pixels.rows().flatten().copied().collect::<Vec<_>>();
+    let xs = solve(&pixels, config).map_err(|cycle| AssembleError::Impossible {
+        row: cycle / config.width,
+        column: cycle % config.width,
+    })?;
+    Ok(compile(&xs))
+}
+
+/// Finds a value of `x` for every cycle that draws the pixels, where `x` starts at 1 and
+/// only changes once it has been held for at least two cycles, the length of an `addx`.
+/// Returns the first cycle that cannot be drawn if there is none.
+fn solve(pixels: &[bool], config: &DisplayConfig) -> Result<Vec<i32>, usize> {
+    // Once the sprite is off either edge of the screen, `x` draws nothing wherever it is
+    let (min_x, max_x) = (
+        -(config.sprite_width as i32),
+        (config.width + config.sprite_width) as i32,
+    );
+    let values = (max_x - min_x + 1) as usize;
+    let index = |x: i32| (x - min_x) as usize;
+    // Cycles are 0-based here, and 1-based in the config
+    let draws = |cycle: usize, x: i32| config.is_lit(cycle + 1, x) == pixels[cycle];
+
+    // The value of `x` in the previous cycle that reaches each value in each cycle, either
+    // as a new value or one that has been held for at least two cycles
+    let mut reached: Vec<Vec<[Option<i32>; 2]>> = vec![vec![[None; 2]; values]; pixels.len()];
+    if !draws(0, 1) {
+        return Err(0);
+    }
+    reached[0][index(1)][0] = Some(1);
+
+    for cycle in 1..pixels.len() {
+        for x in min_x..=max_x {
+            if !draws(cycle, x) {
+                continue;
+            }
+            let previous = &reached[cycle - 1];
+            let held = previous[index(x)].iter().any(Option::is_some);
+            let changed = (min_x..=max_x).find(|&p| p != x && previous[index(p)][1].is_some());
+            reached[cycle][index(x)] = [changed, held.then_some(x)];
+        }
+        if reached[cycle].iter().all(|r| r.iter().all(Option::is_none)) {
+            return Err(cycle);
+        }
+    }
+
+    let last = pixels.len() - 1;
+    let (mut x, mut held) = (min_x..=max_x)
+        .flat_map(|x| [(x, 1), (x, 0)])
+        .find(|&(x, held)| reached[last][index(x)][held].is_some())
+        .unwrap();
+    let mut xs = vec![x; pixels.len()];
+    for cycle in (1..pixels.len()).rev() {
+        xs[cycle] = x;
+        let previous = reached[cycle][index(x)][held].unwrap();
+        held = if previous == x {
+            // Either state of the previous cycle can be held into this one
+            usize::from(reached[cycle - 1][index(x)][1].is_some())
+        } else {
+            1
+        };
+        x = previous;
+    }
+    xs[0] = x;
+    Ok(xs)
+}
+
+/// Emits `noop` while `x` holds its value, and `addx` over the two cycles before it changes
+fn compile(xs: &[i32]) -> Vec<Box<dyn Instruction>> {
+    let noops = |n| (0..n).map(|_| Box::new(Noop) as Box<dyn Instruction>);
+    let mut instructions: Vec<Box<dyn Instruction>> = vec![];
+    let mut start = 0;
+    for cycle in 1..=xs.len() {
+        let next = xs.get(cycle);
+        if next == Some(&xs[start]) {
+            continue;
+        }
+        let held = cycle - start;
+        match next {
+            Some(&x) => {
+                instructions.extend(noops(held - 2));
+                instructions.push(Box::new(Add {
+                    register: 'x',
+                    value: x - xs[start],
+                }));
+            }
+            None => instructions.extend(noops(held)),
+        }
+        start = cycle;
+    }
+    instructions
+}
+
+#[cfg(test)]
+mod test {
+    use super::{assemble, AssembleError};
+    use crate::day_10::cpu::InstructionSet;
+    use crate::day_10::{Device, DisplayConfig, Program};
+    use crate::examples;
+    use crate::Solution;
+
+    #[test]
+    fn test_assemble() {
+        let screen = Device::parse(&examples::input(10, "example"))
+            .unwrap()
+            .run()
+            .screen();
+        let listing = assemble(&screen, &DisplayConfig::default())
+            .unwrap()
+            .iter()
+            .map(|i| format!("{}\n", i))
+            .collect::<String>();
+
+        let instructions = InstructionSet::default().parse_program(&listing).unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        program.execute();
+        assert_eq!(screen, program.screen());
+        assert_eq!(240, program.cpu().cycle());
+    }
+
+    #[test]
+    fn test_impossible() {
+        let screen = format!("#.{}\n", ".".repeat(38)).repeat(6);
+        assert_eq!(
+            Err(AssembleError::Impossible { row: 0, column: 1 }),
+            assemble(&screen, &DisplayConfig::default()).map(|_| ())
+        );
+    }
+}
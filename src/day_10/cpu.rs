@@ -0,0 +1,416 @@
+use crate::parse::{self, Line, ParseError};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// The CPU's registers, named by a single letter. `x` starts at 1 and every other register at 0.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Registers(BTreeMap<char, i32>);
+
+impl Registers {
+    pub fn new() -> Self {
+        Self(BTreeMap::from([('x', 1)]))
+    }
+
+    pub fn get(&self, register: char) -> i32 {
+        self.0.get(&register).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, register: char, value: i32) {
+        self.0.insert(register, value);
+    }
+
+    pub fn x(&self) -> i32 {
+        self.get('x')
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, i32)> + '_ {
+        self.0.iter().map(|(r, v)| (*r, *v))
+    }
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state an instruction acts on when it completes
+pub struct State {
+    pub registers: Registers,
+    /// The index of the next instruction to execute. It has already moved past the
+    /// completing instruction when that instruction executes.
+    pub pc: usize,
+}
+
+impl State {
+    /// Moves the program counter relative to the completing instruction. Jumping before
+    /// the start of the program halts it.
+    pub fn jump(&mut self, offset: isize) {
+        self.pc = (self.pc - 1)
+            .checked_add_signed(offset)
+            .unwrap_or(usize::MAX);
+    }
+}
+
+/// An instruction the CPU can execute
+pub trait Instruction: Debug + Display {
+    /// The number of cycles the instruction takes to complete
+    fn cycles(&self) -> usize;
+
+    /// Applies the instruction's effect at the end of its last cycle
+    fn execute(&self, state: &mut State);
+}
+
+/// `noop`: does nothing for one cycle
+#[derive(Debug)]
+pub struct Noop;
+
+impl Instruction for Noop {
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn execute(&self, _: &mut State) {}
+}
+
+impl Display for Noop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+/// `add<r> V`: adds `V` to register `r` after two cycles, e.g. `addx 3` or `addy -2`, wrapping
+/// around on overflow
+#[derive(Debug)]
+pub struct Add {
+    pub register: char,
+    pub value: i32,
+}
+
+impl Instruction for Add {
+    fn cycles(&self) -> usize {
+        2
+    }
+
+    fn execute(&self, state: &mut State) {
+        let value = state.registers.get(self.register).wrapping_add(self.value);
+        state.registers.set(self.register, value);
+    }
+}
+
+impl Display for Add {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "add{} {}", self.register, self.value)
+    }
+}
+
+/// `mul r V`: multiplies register `r` by `V` after three cycles, wrapping around on overflow
+#[derive(Debug)]
+pub struct Mul {
+    pub register: char,
+    pub value: i32,
+}
+
+impl Instruction for Mul {
+    fn cycles(&self) -> usize {
+        3
+    }
+
+    fn execute(&self, state: &mut State) {
+        let value = state.registers.get(self.register).wrapping_mul(self.value);
+        state.registers.set(self.register, value);
+    }
+}
+
+impl Display for Mul {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mul {} {}", self.register, self.value)
+    }
+}
+
+/// `jmp N`: jumps `N` instructions relative to itself after one cycle
+#[derive(Debug)]
+pub struct Jmp(pub isize);
+
+impl Instruction for Jmp {
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn execute(&self, state: &mut State) {
+        state.jump(self.0);
+    }
+}
+
+impl Display for Jmp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "jmp {:+}", self.0)
+    }
+}
+
+/// Parses the operands of an instruction
+pub type Parser = fn(&Line, &str) -> Result<Box<dyn Instruction>, ParseError>;
+
+/// The instructions a program may use, by mnemonic
+pub struct InstructionSet(HashMap<&'static str, Parser>);
+
+impl InstructionSet {
+    /// An instruction set with no instructions
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// The puzzle's instruction set, `noop` and `addx`
+    pub fn new() -> Self {
+        let mut set = Self::empty();
+        set.register("noop", parse_noop)
+            .register("addx", parse_add::<'x'>);
+        set
+    }
+
+    /// The puzzle's instruction set, along with `addy`, `mul` and `jmp`
+    pub fn extended() -> Self {
+        let mut set = Self::new();
+        set.register("addy", parse_add::<'y'>)
+            .register("mul", parse_mul)
+            .register("jmp", parse_jmp);
+        set
+    }
+
+    pub fn register(&mut self, mnemonic: &'static str, parser: Parser) -> &mut Self {
+        self.0.insert(mnemonic, parser);
+        self
+    }
+
+    pub fn parse(&self, line: &Line) -> Result<Box<dyn Instruction>, ParseError> {
+        let (mnemonic, operands) = line.text.split_once(' ').unwrap_or((line.text, ""));
+        let parser = self
+            .0
+            .get(mnemonic)
+            .ok_or_else(|| line.error(mnemonic, "unknown instruction"))?;
+        parser(line, operands)
+    }
+
+    pub fn parse_program(&self, input: &str) -> Result<Vec<Box<dyn Instruction>>, ParseError> {
+        parse::lines(input).map(|line| self.parse(&line)).collect()
+    }
+}
+
+impl Default for InstructionSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_noop(line: &Line, operands: &str) -> Result<Box<dyn Instruction>, ParseError> {
+    if !operands.is_empty() {
+        return Err(line.error(operands, "unexpected operand"));
+    }
+    Ok(Box::new(Noop))
+}
+
+fn parse_add<const R: char>(
+    line: &Line,
+    operands: &str,
+) -> Result<Box<dyn Instruction>, ParseError> {
+    Ok(Box::new(Add {
+        register: R,
+        value: parse_value(line, operands)?,
+    }))
+}
+
+fn parse_value<T>(line: &Line, operand: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if operand.is_empty() {
+        return Err(line.missing("a value"));
+    }
+    line.parse(operand)
+}
+
+fn parse_register(line: &Line, operand: &str) -> Result<char, ParseError> {
+    let mut chars = operand.chars();
+    match (chars.next(), chars.next()) {
+        (Some(r), None) if r.is_ascii_lowercase() => Ok(r),
+        _ => Err(line.error(operand, "expected a register")),
+    }
+}
+
+fn parse_mul(line: &Line, operands: &str) -> Result<Box<dyn Instruction>, ParseError> {
+    let (register, value) = operands
+        .split_once(' ')
+        .ok_or_else(|| line.missing("a register and a value"))?;
+    Ok(Box::new(Mul {
+        register: parse_register(line, register)?,
+        value: parse_value(line, value)?,
+    }))
+}
+
+fn parse_jmp(line: &Line, operands: &str) -> Result<Box<dyn Instruction>, ParseError> {
+    Ok(Box::new(Jmp(parse_value(line, operands)?)))
+}
+
+/// The state of the CPU during a cycle, before the current instruction completes
+pub struct Cycle<'a> {
+    /// The 1-based number of the cycle
+    pub number: usize,
+    /// The index of the current instruction
+    pub pc: usize,
+    pub instruction: &'a dyn Instruction,
+    pub registers: &'a Registers,
+}
+
+/// Notified during each cycle of the CPU
+pub trait Observer {
+    fn on_cycle(&mut self, cycle: &Cycle);
+}
+
+impl Observer for [&mut dyn Observer] {
+    fn on_cycle(&mut self, cycle: &Cycle) {
+        for observer in self.iter_mut() {
+            observer.on_cycle(cycle);
+        }
+    }
+}
+
+/// Executes a program, one cycle at a time
+pub struct Cpu {
+    instructions: Vec<Box<dyn Instruction>>,
+    state: State,
+    cycle: usize,
+    /// The number of cycles spent so far on the current instruction
+    elapsed: usize,
+}
+
+impl Cpu {
+    pub fn new(instructions: Vec<Box<dyn Instruction>>) -> Self {
+        Self {
+            instructions,
+            state: State {
+                registers: Registers::new(),
+                pc: 0,
+            },
+            cycle: 0,
+            elapsed: 0,
+        }
+    }
+
+    pub fn instructions(&self) -> &[Box<dyn Instruction>] {
+        &self.instructions
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.state.registers
+    }
+
+    /// The index of the current instruction
+    pub fn pc(&self) -> usize {
+        self.state.pc
+    }
+
+    /// The number of completed cycles
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// The current instruction and the number of cycles spent on it so far
+    pub fn current(&self) -> Option<(&dyn Instruction, usize)> {
+        self.instructions
+            .get(self.state.pc)
+            .map(|i| (i.as_ref(), self.elapsed))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.state.pc >= self.instructions.len()
+    }
+
+    /// Runs a single cycle, notifying the observer during it. Returns false if the
+    /// program has already halted.
+    pub fn tick<O: Observer + ?Sized>(&mut self, observer: &mut O) -> bool {
+        let Some(instruction) = self.instructions.get(self.state.pc) else {
+            return false;
+        };
+
+        self.cycle += 1;
+        observer.on_cycle(&Cycle {
+            number: self.cycle,
+            pc: self.state.pc,
+            instruction: instruction.as_ref(),
+            registers: &self.state.registers,
+        });
+
+        self.elapsed += 1;
+        if self.elapsed >= instruction.cycles() {
+            self.elapsed = 0;
+            self.state.pc += 1;
+            instruction.execute(&mut self.state);
+        }
+        true
+    }
+
+    /// Runs until the program halts. A program that jumps backwards may never halt.
+    pub fn run<O: Observer + ?Sized>(&mut self, observer: &mut O) {
+        while self.tick(observer) {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cpu, Cycle, InstructionSet, Observer};
+
+    struct Xs(Vec<i32>);
+
+    impl Observer for Xs {
+        fn on_cycle(&mut self, cycle: &Cycle) {
+            self.0.push(cycle.registers.x());
+        }
+    }
+
+    #[test]
+    fn test_cycles() {
+        let program = InstructionSet::new()
+            .parse_program("noop\naddx 3\naddx -5")
+            .unwrap();
+        let mut cpu = Cpu::new(program);
+        let mut xs = Xs(vec![]);
+        cpu.run(&mut xs);
+        assert_eq!(vec![1, 1, 1, 4, 4], xs.0);
+        assert_eq!(-1, cpu.registers().x());
+        assert_eq!(5, cpu.cycle());
+    }
+
+    #[test]
+    fn test_extended() {
+        let program = InstructionSet::extended()
+            .parse_program("addy 2\nmul y 3\naddx 1\njmp +2\naddx 100\nmul x 2")
+            .unwrap();
+        let mut cpu = Cpu::new(program);
+        let mut xs = Xs(vec![]);
+        cpu.run(&mut xs);
+        assert_eq!(6, cpu.registers().get('y'));
+        assert_eq!(4, cpu.registers().x());
+        assert_eq!(2 + 3 + 2 + 1 + 3, cpu.cycle());
+    }
+
+    #[test]
+    fn test_unknown_instruction() {
+        let err = InstructionSet::new()
+            .parse_program("noop\nmul x 2")
+            .unwrap_err();
+        assert_eq!(
+            "line 2, column 1: unknown instruction \"mul\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_missing_value() {
+        let err = InstructionSet::new()
+            .parse_program("noop\naddx")
+            .unwrap_err();
+        assert_eq!("line 2, column 5: expected a value \"\"", err.to_string());
+    }
+}
@@ -0,0 +1,433 @@
+use super::cpu::Registers;
+use super::Program;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+/// Where the debugger stops a running program
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Breakpoint {
+    /// Before the cycle with this number runs
+    Cycle(usize),
+    /// When the register changes to this value
+    Register(char, i32),
+}
+
+impl Breakpoint {
+    /// Whether this is a cycle breakpoint on the cycle about to run
+    fn is_due(&self, program: &Program) -> bool {
+        let cpu = program.cpu();
+        matches!(*self, Breakpoint::Cycle(cycle) if cpu.cycle() + 1 == cycle && !cpu.is_halted())
+    }
+
+    /// Whether this is a register breakpoint hit by the cycle that just ran
+    fn is_hit(&self, program: &Program, before: &Registers) -> bool {
+        match *self {
+            Breakpoint::Cycle(_) => false,
+            Breakpoint::Register(register, value) => {
+                program.cpu().registers().get(register) == value && before.get(register) != value
+            }
+        }
+    }
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Breakpoint::Cycle(cycle) => write!(f, "cycle {}", cycle),
+            Breakpoint::Register(register, value) => write!(f, "{} = {}", register, value),
+        }
+    }
+}
+
+/// A debugger command, as typed at the prompt
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Command {
+    /// `step [n]`: run `n` cycles
+    Step(usize),
+    /// `next [n]`: run until `n` instructions have completed
+    Next(usize),
+    /// `break cycle <n>` or `break <register> <value>`
+    Break(Breakpoint),
+    /// `delete <n>`: remove the `n`th breakpoint
+    Delete(usize),
+    /// `breakpoints`: list the breakpoints
+    Breakpoints,
+    /// `continue`: run until a breakpoint is hit or the program halts
+    Continue,
+    /// `finish`: run until the program halts, ignoring breakpoints
+    Finish,
+    /// `print`: show the cycle, registers, pending instruction and current row
+    Print,
+    /// `screen`: show everything drawn so far
+    Screen,
+    Help,
+    Quit,
+}
+
+pub const HELP: &str = "\
+step [n]              run n cycles (s)
+next [n]              run n instructions (n)
+break cycle <n>       stop before cycle n runs (b)
+break <register> <v>  stop when a register changes to v
+delete <n>            remove breakpoint n
+breakpoints           list breakpoints
+continue              run to the next breakpoint (c)
+finish                run to the end, ignoring breakpoints
+print                 show the cycle, registers, instruction and row (p)
+screen                show the screen drawn so far
+quit                  exit the debugger (q)";
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        let number = |word: &str| {
+            word.parse::<usize>()
+                .map_err(|_| format!("expected a number, found `{}`", word))
+        };
+        let count = |words: &[&str]| match words {
+            [] => Ok(1),
+            [n] => number(n),
+            _ => Err(format!("unexpected `{}`", words[1..].join(" "))),
+        };
+
+        match words.as_slice() {
+            ["step" | "s", rest @ ..] => count(rest).map(Command::Step),
+            ["next" | "n", rest @ ..] => count(rest).map(Command::Next),
+            ["break" | "b", "cycle", n] => number(n).map(|n| Command::Break(Breakpoint::Cycle(n))),
+            ["break" | "b", register, value] => {
+                let mut chars = register.chars();
+                let register = match (chars.next(), chars.next()) {
+                    (Some(r), None) if r.is_ascii_lowercase() => r,
+                    _ => return Err(format!("expected a register, found `{}`", register)),
+                };
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("expected a value, found `{}`", value))?;
+                Ok(Command::Break(Breakpoint::Register(register, value)))
+            }
+            ["break" | "b", ..] => {
+                Err("expected `break cycle <n>` or `break <register> <value>`".into())
+            }
+            ["delete" | "d", n] => number(n).map(Command::Delete),
+            ["breakpoints"] => Ok(Command::Breakpoints),
+            ["continue" | "c"] => Ok(Command::Continue),
+            ["finish"] => Ok(Command::Finish),
+            ["print" | "p"] => Ok(Command::Print),
+            ["screen"] => Ok(Command::Screen),
+            ["help" | "h"] => Ok(Command::Help),
+            ["quit" | "q"] => Ok(Command::Quit),
+            [] => Err("expected a command".into()),
+            [command, ..] => Err(format!("unknown command `{}`, try `help`", command)),
+        }
+    }
+}
+
+/// Why running a program stopped
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Stop {
+    /// At the breakpoint with this index
+    Breakpoint(usize),
+    Halted,
+    /// After running the most cycles allowed at once, with the program still running
+    Limit,
+}
+
+/// Steps through a program, stopping at breakpoints
+pub struct Debugger {
+    program: Program,
+    breakpoints: Vec<Breakpoint>,
+    /// The number of cycles run when the last resume stopped before a cycle breakpoint
+    stopped_at: Option<usize>,
+    /// The most cycles `resume` and `finish` run at once, so a program that loops forever
+    /// still stops
+    max_cycles: usize,
+}
+
+impl Debugger {
+    /// Debugs a program, running at most as many cycles at once as it takes to fill the screen
+    pub fn new(program: Program) -> Self {
+        let config = program.config();
+        let max_cycles = config.width * config.height;
+        Self {
+            program,
+            breakpoints: vec![],
+            stopped_at: None,
+            max_cycles,
+        }
+    }
+
+    /// Sets the most cycles `resume` and `finish` run at once
+    pub fn set_max_cycles(&mut self, max_cycles: usize) {
+        self.max_cycles = max_cycles;
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    /// Runs a single cycle, returning false if the program has halted
+    pub fn step_cycle(&mut self) -> bool {
+        self.program.tick()
+    }
+
+    /// Runs until the current instruction completes, returning false if the program has halted
+    pub fn step_instruction(&mut self) -> bool {
+        if !self.program.tick() {
+            return false;
+        }
+        while self
+            .program
+            .cpu()
+            .current()
+            .is_some_and(|(_, elapsed)| elapsed > 0)
+        {
+            self.program.tick();
+        }
+        true
+    }
+
+    /// Runs until a breakpoint is hit, the program halts or the cycle limit is reached
+    pub fn resume(&mut self) -> Stop {
+        // A cycle breakpoint is still due once it has stopped the program, so it is passed over
+        // to move on, but only if the program has not been stepped since
+        let mut resuming = self.stopped_at.take() == Some(self.program.cpu().cycle());
+        for _ in 0..self.max_cycles {
+            if !resuming {
+                if let Some(index) = self
+                    .breakpoints
+                    .iter()
+                    .position(|b| b.is_due(&self.program))
+                {
+                    self.stopped_at = Some(self.program.cpu().cycle());
+                    return Stop::Breakpoint(index);
+                }
+            }
+            resuming = false;
+
+            let before = self.program.cpu().registers().clone();
+            if !self.program.tick() {
+                return Stop::Halted;
+            }
+            if let Some(index) = self
+                .breakpoints
+                .iter()
+                .position(|b| b.is_hit(&self.program, &before))
+            {
+                return Stop::Breakpoint(index);
+            }
+        }
+        self.limit()
+    }
+
+    /// Runs until the program halts or the cycle limit is reached, ignoring breakpoints
+    pub fn finish(&mut self) -> Stop {
+        for _ in 0..self.max_cycles {
+            if !self.program.tick() {
+                return Stop::Halted;
+            }
+        }
+        self.limit()
+    }
+
+    fn limit(&self) -> Stop {
+        if self.program.cpu().is_halted() {
+            Stop::Halted
+        } else {
+            Stop::Limit
+        }
+    }
+
+    /// The cycle about to run, the registers, the pending instruction and the current row
+    pub fn state(&self) -> String {
+        let cpu = self.program.cpu();
+        let mut state = String::new();
+        match cpu.current() {
+            Some(_) => writeln!(&mut state, "cycle {}", cpu.cycle() + 1).unwrap(),
+            None => writeln!(&mut state, "halted after {} cycles", cpu.cycle()).unwrap(),
+        }
+        let registers = cpu
+            .registers()
+            .iter()
+            .map(|(r, v)| format!("{} = {}", r, v))
+            .collect::<Vec<_>>();
+        writeln!(&mut state, "registers: {}", registers.join(", ")).unwrap();
+        if let Some((instruction, elapsed)) = cpu.current() {
+            writeln!(
+                &mut state,
+                "instruction {}: {} (cycle {} of {})",
+                cpu.pc() + 1,
+                instruction,
+                elapsed + 1,
+                instruction.cycles()
+            )
+            .unwrap();
+        }
+        write!(&mut state, "row: {}", self.program.row()).unwrap();
+        state
+    }
+
+    fn still_running(&self) -> String {
+        format!(
+            "stopped after {} cycles, program still running",
+            self.max_cycles
+        )
+    }
+
+    /// Runs a command, returning what to show for it
+    pub fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::Step(cycles) => {
+                for _ in 0..cycles {
+                    if !self.step_cycle() {
+                        break;
+                    }
+                }
+                self.state()
+            }
+            Command::Next(instructions) => {
+                for _ in 0..instructions {
+                    if !self.step_instruction() {
+                        break;
+                    }
+                }
+                self.state()
+            }
+            Command::Break(breakpoint) => {
+                self.add_breakpoint(breakpoint);
+                format!("breakpoint {}: {}", self.breakpoints.len(), breakpoint)
+            }
+            Command::Delete(n) => match n.checked_sub(1).and_then(|i| self.remove_breakpoint(i)) {
+                Some(breakpoint) => format!("deleted breakpoint {}: {}", n, breakpoint),
+                None => format!("no breakpoint {}", n),
+            },
+            Command::Breakpoints => self
+                .breakpoints
+                .iter()
+                .enumerate()
+                .map(|(i, b)| format!("{}: {}", i + 1, b))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Command::Continue => match self.resume() {
+                Stop::Breakpoint(i) => format!(
+                    "hit breakpoint {}: {}\n{}",
+                    i + 1,
+                    self.breakpoints[i],
+                    self.state()
+                ),
+                Stop::Halted => self.state(),
+                Stop::Limit => format!("{}\n{}", self.still_running(), self.state()),
+            },
+            Command::Finish => match self.finish() {
+                Stop::Limit => format!("{}\n{}", self.still_running(), self.state()),
+                _ => format!("{}\n{}", self.state(), self.program.screen().trim_end()),
+            },
+            Command::Print => self.state(),
+            Command::Screen => self.program.screen().trim_end().to_string(),
+            Command::Help => HELP.to_string(),
+            Command::Quit => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Breakpoint, Command, Debugger, Stop};
+    use crate::day_10::cpu::InstructionSet;
+    use crate::day_10::{DisplayConfig, Program};
+
+    const TEST_INPUT: &str = "noop\naddx 3\naddx -5\nnoop";
+
+    fn debugger() -> Debugger {
+        let instructions = InstructionSet::default().parse_program(TEST_INPUT).unwrap();
+        Debugger::new(Program::new(instructions, DisplayConfig::default()))
+    }
+
+    #[test]
+    fn test_stepping() {
+        let mut debugger = debugger();
+        debugger.execute(Command::Step(2));
+        assert_eq!(
+            "cycle 3\nregisters: x = 1\ninstruction 2: addx 3 (cycle 2 of 2)\nrow: ##",
+            debugger.state()
+        );
+        debugger.execute(Command::Next(2));
+        assert_eq!(
+            "cycle 6\nregisters: x = -1\ninstruction 4: noop (cycle 1 of 1)\nrow: #####",
+            debugger.state()
+        );
+        debugger.execute(Command::Next(2));
+        assert_eq!(
+            "halted after 6 cycles\nregisters: x = -1\nrow: #####.",
+            debugger.state()
+        );
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = debugger();
+        debugger.add_breakpoint(Breakpoint::Register('x', -1));
+        debugger.add_breakpoint(Breakpoint::Cycle(3));
+        assert_eq!(Stop::Breakpoint(1), debugger.resume());
+        assert_eq!(2, debugger.program().cpu().cycle());
+        assert_eq!(Stop::Breakpoint(0), debugger.resume());
+        assert_eq!(5, debugger.program().cpu().cycle());
+        assert_eq!(Stop::Halted, debugger.resume());
+        assert!(debugger.program().cpu().is_halted());
+
+        let mut debugger = self::debugger();
+        debugger.add_breakpoint(Breakpoint::Cycle(1));
+        assert_eq!(Stop::Breakpoint(0), debugger.resume());
+        assert_eq!(0, debugger.program().cpu().cycle());
+        assert_eq!(Stop::Halted, debugger.resume());
+
+        let mut debugger = self::debugger();
+        debugger.step_cycle();
+        debugger.add_breakpoint(Breakpoint::Cycle(2));
+        assert_eq!(Stop::Breakpoint(0), debugger.resume());
+        assert_eq!(1, debugger.program().cpu().cycle());
+    }
+
+    #[test]
+    fn test_cycle_limit() {
+        let instructions = InstructionSet::extended()
+            .parse_program("noop\njmp -1")
+            .unwrap();
+        let mut debugger = Debugger::new(Program::new(instructions, DisplayConfig::default()));
+        assert_eq!(Stop::Limit, debugger.resume());
+        assert_eq!(240, debugger.program().cpu().cycle());
+        debugger.set_max_cycles(10);
+        assert!(debugger
+            .execute(Command::Finish)
+            .starts_with("stopped after 10 cycles, program still running\ncycle 251\n"));
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Ok(Command::Step(1)), "s".parse());
+        assert_eq!(Ok(Command::Next(3)), "next 3".parse());
+        assert_eq!(
+            Ok(Command::Break(Breakpoint::Cycle(20))),
+            "break cycle 20".parse()
+        );
+        assert_eq!(
+            Ok(Command::Break(Breakpoint::Register('x', -4))),
+            "b x -4".parse()
+        );
+        assert!("break xy 1".parse::<Command>().is_err());
+        assert!("jump".parse::<Command>().is_err());
+    }
+}
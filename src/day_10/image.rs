@@ -0,0 +1,292 @@
+use super::trace::Step;
+use super::{DisplayConfig, Program};
+use crate::grid::Grid;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Pixel {
+    Dark,
+    Lit,
+    /// Not yet drawn, but covered by the sprite on the row the beam is drawing
+    Sprite,
+    /// Being drawn by the beam
+    Beam {
+        lit: bool,
+    },
+}
+
+impl Pixel {
+    fn rgb(&self) -> [u8; 3] {
+        match self {
+            Pixel::Dark => [16, 16, 32],
+            Pixel::Lit => [255, 204, 0],
+            Pixel::Sprite => [64, 64, 128],
+            Pixel::Beam { lit: true } => [255, 96, 64],
+            Pixel::Beam { lit: false } => [128, 32, 32],
+        }
+    }
+
+    fn is_lit(&self) -> bool {
+        matches!(self, Pixel::Lit | Pixel::Beam { lit: true })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// Black and white, with lit pixels in black
+    Pbm,
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Pbm, Format::Ppm, Format::Png];
+
+    /// The format a file's extension names, if any
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|f| f.extension() == extension)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Pbm => "pbm",
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(Format::Pbm),
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            _ => Err(format!("expected `pbm`, `ppm` or `png`, found `{}`", s)),
+        }
+    }
+}
+
+/// The pixels a program has drawn, leaving any it has not drawn dark
+pub fn screen(program: &Program) -> Grid<Pixel> {
+    let config = program.config();
+    let mut pixels = Grid::new(config.width, config.height, Pixel::Dark);
+    for (y, row) in program.screen().lines().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == '#' {
+                pixels[(x, y)] = Pixel::Lit;
+            }
+        }
+    }
+    pixels
+}
+
+/// One frame for each cycle of a trace, showing what the beam has drawn so far, the pixel it
+/// is drawing and where the sprite is on its row
+pub fn frames<'a>(
+    trace: &'a [Step],
+    config: &'a DisplayConfig,
+) -> impl Iterator<Item = Grid<Pixel>> + 'a {
+    let mut pixels = Grid::new(config.width, config.height, Pixel::Dark);
+    trace
+        .iter()
+        .take(config.width * config.height)
+        .map(move |step| {
+            let (x, y) = (
+                (step.cycle - 1) % config.width,
+                (step.cycle - 1) / config.width,
+            );
+            let lit = step.pixel == Some('#');
+
+            let mut frame = pixels.clone();
+            for column in config.sprite(step.sprite) {
+                if column > x as i32 && (column as usize) < config.width {
+                    frame[(column as usize, y)] = Pixel::Sprite;
+                }
+            }
+            frame[(x, y)] = Pixel::Beam { lit };
+            pixels[(x, y)] = if lit { Pixel::Lit } else { Pixel::Dark };
+            frame
+        })
+}
+
+/// Every pixel of an image, row by row, each repeated to draw it as a `scale` by `scale` square
+fn scaled(pixels: &Grid<Pixel>, scale: usize) -> impl Iterator<Item = &Pixel> {
+    pixels.rows().flat_map(move |row| {
+        std::iter::repeat_n(row, scale).flat_map(move |row| {
+            row.iter()
+                .flat_map(move |pixel| std::iter::repeat_n(pixel, scale))
+        })
+    })
+}
+
+/// Writes the pixels as an image, each pixel drawn as a `scale` by `scale` square
+pub fn write(
+    pixels: &Grid<Pixel>,
+    format: Format,
+    scale: usize,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let (width, height) = (pixels.width() * scale, pixels.height() * scale);
+    let scaled = scaled(pixels, scale);
+
+    match format {
+        Format::Pbm => {
+            write!(writer, "P4\n{} {}\n", width, height)?;
+            let rows = scaled.collect::<Vec<_>>();
+            for row in rows.chunks(width.max(1)) {
+                let bytes = row.chunks(8).map(|bits| {
+                    bits.iter()
+                        .enumerate()
+                        .filter(|(_, pixel)| pixel.is_lit())
+                        .fold(0u8, |byte, (i, _)| byte | 0x80 >> i)
+                });
+                writer.write_all(&bytes.collect::<Vec<_>>())?;
+            }
+        }
+        Format::Ppm => {
+            write!(writer, "P6\n{} {}\n255\n", width, height)?;
+            writer.write_all(&scaled.flat_map(|pixel| pixel.rgb()).collect::<Vec<_>>())?;
+        }
+        Format::Png => {
+            let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut png = encoder.write_header().map_err(io::Error::other)?;
+            png.write_image_data(&scaled.flat_map(|pixel| pixel.rgb()).collect::<Vec<_>>())
+                .map_err(io::Error::other)?;
+            png.finish().map_err(io::Error::other)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes frames of the same size as an animated PNG, showing each for `delay_ms` and playing
+/// them once
+pub fn write_animation(
+    frames: &[Grid<Pixel>],
+    scale: usize,
+    delay_ms: u16,
+    writer: impl Write,
+) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "an animation needs at least one frame",
+        ));
+    };
+    let (width, height) = (first.width() * scale, first.height() * scale);
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 1)
+        .map_err(io::Error::other)?;
+    encoder
+        .set_frame_delay(delay_ms, 1000)
+        .map_err(io::Error::other)?;
+    let mut png = encoder.write_header().map_err(io::Error::other)?;
+    for frame in frames {
+        png.write_image_data(
+            &scaled(frame, scale)
+                .flat_map(|pixel| pixel.rgb())
+                .collect::<Vec<_>>(),
+        )
+        .map_err(io::Error::other)?;
+    }
+    png.finish().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{frames, screen, write, write_animation, Format, Pixel};
+    use crate::day_10::cpu::InstructionSet;
+    use crate::day_10::{DisplayConfig, Program};
+    use crate::grid::Grid;
+    use std::path::Path;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            Some(Format::Pbm),
+            Format::from_path(Path::new("screen.pbm"))
+        );
+        assert_eq!(
+            Some(Format::Png),
+            Format::from_path(Path::new("out/screen.PNG"))
+        );
+        assert_eq!(None, Format::from_path(Path::new("screen.gif")));
+        assert_eq!(None, Format::from_path(Path::new("screen")));
+    }
+
+    #[test]
+    fn test_write() {
+        let pixels = Grid::parse("#..\n.#.", |c| {
+            Some(if c == '#' { Pixel::Lit } else { Pixel::Dark })
+        })
+        .unwrap();
+        let mut pbm = vec![];
+        write(&pixels, Format::Pbm, 3, &mut pbm).unwrap();
+        assert_eq!(b"P4\n9 6\n", &pbm[..7]);
+        assert_eq!(
+            vec![
+                0b1110_0000,
+                0,
+                0b1110_0000,
+                0,
+                0b1110_0000,
+                0,
+                0b0001_1100,
+                0
+            ],
+            pbm[7..15]
+        );
+
+        let mut ppm = vec![];
+        write(&pixels, Format::Ppm, 1, &mut ppm).unwrap();
+        assert_eq!(b"P6\n3 2\n255\n".len() + 3 * 2 * 3, ppm.len());
+
+        let mut png = vec![];
+        write(&pixels, Format::Png, 2, &mut png).unwrap();
+        assert_eq!(b"\x89PNG", &png[..4]);
+
+        let mut apng = vec![];
+        write_animation(&[pixels.clone(), pixels], 2, 50, &mut apng).unwrap();
+        assert!(apng.windows(4).any(|chunk| chunk == b"acTL"));
+        assert_eq!(2, apng.windows(4).filter(|chunk| chunk == b"fcTL").count());
+    }
+
+    #[test]
+    fn test_frames() {
+        let instructions = InstructionSet::default()
+            .parse_program("noop\naddx 3\naddx -5")
+            .unwrap();
+        let config = DisplayConfig::default();
+        let mut program = Program::new(instructions, config);
+        program.enable_trace();
+        program.execute();
+
+        let frames = frames(program.trace().unwrap(), &config).collect::<Vec<_>>();
+        assert_eq!(5, frames.len());
+        let (lit, dark) = (Pixel::Lit, Pixel::Dark);
+        assert_eq!(
+            &[lit, lit, lit, lit, lit, dark],
+            &screen(&program).row(0).unwrap()[..6]
+        );
+        assert_eq!(
+            &[
+                Pixel::Lit,
+                Pixel::Lit,
+                Pixel::Lit,
+                Pixel::Beam { lit: true },
+                Pixel::Sprite
+            ],
+            &frames[3].row(0).unwrap()[..5]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use serde::Serialize;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// What happened during a single cycle
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Step {
+    pub cycle: usize,
+    pub instruction: String,
+    /// `x` during the cycle
+    pub x_before: i32,
+    /// `x` once the cycle has finished, after any instruction completing during it
+    pub x_after: i32,
+    /// The column the sprite is centered on
+    pub sprite: i32,
+    /// The pixel drawn during the cycle, if the screen was not already full
+    pub pixel: Option<char>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Csv,
+    JsonLines,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "jsonl" | "json" => Ok(Format::JsonLines),
+            _ => Err(format!("expected `csv` or `jsonl`, found `{}`", s)),
+        }
+    }
+}
+
+/// Writes a trace as CSV with a header row, or as one JSON object per line
+pub fn write(trace: &[Step], format: Format, writer: impl Write) -> io::Result<()> {
+    let mut writer = Writer::new(format, writer)?;
+    for step in trace {
+        writer.write(step)?;
+    }
+    writer.flush()
+}
+
+/// Writes the steps of a trace as they happen, so a trace never has to be held in memory
+pub struct Writer<W: Write> {
+    format: Format,
+    writer: W,
+}
+
+impl<W: Write> Writer<W> {
+    /// Starts a trace, writing the header row of a CSV trace
+    pub fn new(format: Format, mut writer: W) -> io::Result<Self> {
+        if format == Format::Csv {
+            writeln!(writer, "cycle,instruction,x_before,x_after,sprite,pixel")?;
+        }
+        Ok(Self { format, writer })
+    }
+
+    pub fn write(&mut self, step: &Step) -> io::Result<()> {
+        match self.format {
+            Format::Csv => writeln!(
+                self.writer,
+                "{},{},{},{},{},{}",
+                step.cycle,
+                step.instruction,
+                step.x_before,
+                step.x_after,
+                step.sprite,
+                step.pixel.map(String::from).unwrap_or_default()
+            ),
+            Format::JsonLines => {
+                serde_json::to_writer(&mut self.writer, step)?;
+                writeln!(self.writer)
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write, Format, Writer};
+    use crate::day_10::cpu::InstructionSet;
+    use crate::day_10::{DisplayConfig, Program};
+
+    fn trace(format: Format) -> String {
+        let instructions = InstructionSet::default()
+            .parse_program("noop\naddx 3")
+            .unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        program.enable_trace();
+        program.execute();
+        let mut output = vec![];
+        write(program.trace().unwrap(), format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "cycle,instruction,x_before,x_after,sprite,pixel\n\
+             1,noop,1,1,1,#\n\
+             2,addx 3,1,1,1,#\n\
+             3,addx 3,1,4,1,#\n",
+            trace(Format::Csv)
+        );
+    }
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(
+            r##"{"cycle":3,"instruction":"addx 3","x_before":1,"x_after":4,"sprite":1,"pixel":"#"}"##,
+            trace(Format::JsonLines).lines().last().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_writer() {
+        let instructions = InstructionSet::extended()
+            .parse_program("noop\njmp -1")
+            .unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        let mut output = vec![];
+        let mut writer = Writer::new(Format::JsonLines, &mut output).unwrap();
+        for _ in 0..5 {
+            writer.write(&program.step().unwrap()).unwrap();
+        }
+        writer.flush().unwrap();
+        assert!(program.trace().is_none());
+        assert_eq!(5, String::from_utf8(output).unwrap().lines().count());
+    }
+
+    #[test]
+    fn test_screen_full() {
+        let instructions = InstructionSet::extended()
+            .parse_program("noop\njmp -1")
+            .unwrap();
+        let mut program = Program::new(instructions, DisplayConfig::default());
+        for _ in 0..240 {
+            assert!(program.step().unwrap().pixel.is_some());
+        }
+        let step = program.step().unwrap();
+        assert_eq!(None, step.pixel);
+
+        let mut csv = vec![];
+        write(std::slice::from_ref(&step), Format::Csv, &mut csv).unwrap();
+        assert!(String::from_utf8(csv)
+            .unwrap()
+            .ends_with("\n241,noop,1,1,1,\n"));
+        let mut json = vec![];
+        write(&[step], Format::JsonLines, &mut json).unwrap();
+        assert!(String::from_utf8(json).unwrap().ends_with(
+            r#""pixel":null}
+"#
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::parse::{self, Line, ParseError};
+use crate::Solution;
+use std::collections::VecDeque;
+
+#[derive(Debug, Copy, Clone)]
+enum Operand {
+    Old,
+    Value(u64),
+}
+
+impl Operand {
+    fn parse(line: &Line, input: &str) -> Result<Self, ParseError> {
+        match input {
+            "old" => Ok(Operand::Old),
+            _ => line.parse(input).map(Operand::Value),
+        }
+    }
+
+    fn value(&self, old: u64) -> u64 {
+        match self {
+            Operand::Old => old,
+            Operand::Value(v) => *v,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+/// The operation a monkey applies to an item's worry level when inspecting it
+#[derive(Debug, Copy, Clone)]
+pub struct Operation {
+    lhs: Operand,
+    operator: Operator,
+    rhs: Operand,
+}
+
+impl Operation {
+    pub fn parse(line: &Line, input: &str) -> Result<Self, ParseError> {
+        let expression = input
+            .strip_prefix("new = ")
+            .ok_or_else(|| line.error(input, "expected `new = `"))?;
+        // The fourth token is whatever follows the expression, which should be nothing
+        let mut tokens = expression.splitn(4, ' ');
+        let mut token = |what| tokens.next().ok_or_else(|| line.missing(what));
+        let lhs = Operand::parse(line, token("an operand")?)?;
+        let operator = match token("an operator")? {
+            "+" => Operator::Add,
+            "*" => Operator::Multiply,
+            op => return Err(line.error(op, "unknown operator")),
+        };
+        let rhs = Operand::parse(line, token("an operand")?)?;
+        if let Some(rest) = tokens.next() {
+            return Err(line.error(rest, "unexpected token"));
+        }
+        Ok(Self { lhs, operator, rhs })
+    }
+
+    pub fn apply(&self, old: u64) -> u64 {
+        let (lhs, rhs) = (self.lhs.value(old), self.rhs.value(old));
+        match self.operator {
+            Operator::Add => lhs + rhs,
+            Operator::Multiply => lhs * rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: VecDeque<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl Monkey {
+    /// Parses the monkey `id` from its block of lines, checking that it only
+    /// throws to one of the other `monkeys`
+    pub fn parse(id: usize, block: &[Line], monkeys: usize) -> Result<Self, ParseError> {
+        const FIELDS: [&str; 6] = [
+            "Monkey",
+            "Starting items:",
+            "Operation:",
+            "Test: divisible by",
+            "If true: throw to monkey",
+            "If false: throw to monkey",
+        ];
+
+        let mut values = vec![];
+        for (i, prefix) in FIELDS.iter().enumerate() {
+            let line = block.get(i).ok_or_else(|| {
+                block
+                    .last()
+                    .unwrap()
+                    .missing(&format!("a line starting with `{}`", prefix))
+            })?;
+            let text = line.text.trim_start();
+            let value = text
+                .strip_prefix(prefix)
+                .ok_or_else(|| line.error(text, format!("expected `{}`", prefix)))?;
+            values.push((line, value.trim_start()));
+        }
+
+        let (line, header) = values[0];
+        let number = header
+            .strip_suffix(':')
+            .ok_or_else(|| line.missing("`:`"))?;
+        if line.parse::<usize>(number)? != id {
+            return Err(line.error(number, format!("expected monkey {}", id)));
+        }
+
+        let (line, items) = values[1];
+        let items = items
+            .split(", ")
+            .filter(|item| !item.is_empty())
+            .map(|item| line.parse(item))
+            .collect::<Result<_, _>>()?;
+
+        let (line, operation) = values[2];
+        let operation = Operation::parse(line, operation)?;
+
+        let (line, divisor) = values[3];
+        let divisor = match line.parse(divisor)? {
+            0 => return Err(line.error(divisor, "divisor must be greater than zero")),
+            d => d,
+        };
+
+        let target = |(line, target): (&Line, &str)| match line.parse(target)? {
+            t if t == id => Err(line.error(target, "monkey cannot throw to itself")),
+            t if t >= monkeys => Err(line.error(target, "unknown monkey")),
+            t => Ok(t),
+        };
+
+        Ok(Self {
+            items,
+            operation,
+            divisor,
+            if_true: target(values[4])?,
+            if_false: target(values[5])?,
+        })
+    }
+
+    fn target(&self, worry: u64) -> usize {
+        if worry.is_multiple_of(self.divisor) {
+            self.if_true
+        } else {
+            self.if_false
+        }
+    }
+}
+
+/// How worry levels are kept manageable after a monkey inspects an item
+#[derive(Debug, Copy, Clone)]
+pub enum Relief {
+    /// Worry levels are divided by three
+    DivideByThree,
+    /// Worry levels are kept modulo the given value, typically the product of
+    /// all monkeys' divisors, which preserves every divisibility test
+    Modulo(u64),
+}
+
+pub struct Monkeys(Vec<Monkey>);
+
+impl Monkeys {
+    /// Simulates the given number of rounds, returning how many items each monkey inspected
+    pub fn simulate(&self, rounds: usize, relief: Relief) -> Vec<usize> {
+        let mut monkeys = self.0.clone();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..rounds {
+            for i in 0..monkeys.len() {
+                while let Some(item) = monkeys[i].items.pop_front() {
+                    inspections[i] += 1;
+                    let mut worry = monkeys[i].operation.apply(item);
+                    match relief {
+                        Relief::DivideByThree => worry /= 3,
+                        Relief::Modulo(m) => worry %= m,
+                    }
+                    let target = monkeys[i].target(worry);
+                    monkeys[target].items.push_back(worry);
+                }
+            }
+        }
+        inspections
+    }
+
+    pub fn monkey_business(&self, rounds: usize, relief: Relief) -> usize {
+        let mut inspections = self.simulate(rounds, relief);
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product()
+    }
+
+    fn common_divisor(&self) -> u64 {
+        self.0.iter().map(|m| m.divisor).product()
+    }
+}
+
+impl Solution for Monkeys {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = parse::lines(input).collect::<Vec<_>>();
+        let blocks = lines
+            .split(|line| line.text.trim().is_empty())
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Err(ParseError::new(lines.len() + 1, 1, "", "expected a monkey"));
+        }
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(id, block)| Monkey::parse(id, block, blocks.len()))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part_1(&self) -> usize {
+        self.monkey_business(20, Relief::DivideByThree)
+    }
+
+    fn part_2(&self) -> usize {
+        self.monkey_business(10_000, Relief::Modulo(self.common_divisor()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Monkeys, Relief};
+    use crate::examples::{self, examples};
+    use crate::parse::ParseError;
+    use crate::Solution;
+
+    examples!(Monkeys, 11);
+
+    #[test]
+    fn test_inspections() {
+        let monkeys = Monkeys::parse(&examples::input(11, "example")).unwrap();
+        assert_eq!(
+            vec![101, 95, 7, 105],
+            monkeys.simulate(20, Relief::DivideByThree)
+        );
+    }
+
+    #[test]
+    fn test_unknown_target() {
+        let input = examples::input(11, "example")
+            .replace("If false: throw to monkey 1", "If false: throw to monkey 4");
+        assert_eq!(
+            Err(ParseError::new(27, 31, "4", "unknown monkey")),
+            Monkeys::parse(&input).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_trailing_tokens() {
+        let input = examples::input(11, "example").replace("old * 19", "old * 19 + 3");
+        assert_eq!(
+            Err(ParseError::new(3, 29, "+ 3", "unexpected token")),
+            Monkeys::parse(&input).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_no_monkeys() {
+        assert_eq!(
+            Err(ParseError::new(1, 1, "", "expected a monkey")),
+            Monkeys::parse("").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(3, 1, "", "expected a monkey")),
+            Monkeys::parse("\n  \n").map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub fn parse_opp(input: &str) -> Option<Self> {
+        match input {
+            "A" => Some(Self::Rock),
+            "B" => Some(Self::Paper),
+            "C" => Some(Self::Scissors),
+            _ => None,
+        }
+    }
+
+    pub fn wins_against(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    pub fn loses_against(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+}
+
+/// The second column of the strategy guide, whose meaning differs between parts
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "X" => Some(Self::X),
+            "Y" => Some(Self::Y),
+            "Z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+struct Game((Shape, Shape));
+
+impl Game {
+    pub fn score(&self) -> usize {
+        self.score_shape() + self.score_win()
+    }
+
+    fn score_shape(&self) -> usize {
+        match self.0 .1 {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+
+    fn score_win(&self) -> usize {
+        match &self.0 {
+            (Shape::Rock, Shape::Scissors) => 0,
+            (Shape::Rock, Shape::Paper) => 6,
+            (Shape::Scissors, Shape::Paper) => 0,
+            (Shape::Scissors, Shape::Rock) => 6,
+            (Shape::Paper, Shape::Rock) => 0,
+            (Shape::Paper, Shape::Scissors) => 6,
+            _ => 3,
+        }
+    }
+}
+
+pub struct StrategyGuide(Vec<(Shape, Column)>);
+
+impl Solution for StrategyGuide {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse::lines(input)
+            .map(|line| {
+                let mut inputs = line.text.splitn(3, ' ');
+                let opp = inputs.next().unwrap_or_default();
+                let opp = Shape::parse_opp(opp)
+                    .ok_or_else(|| line.error(opp, "unknown opponent shape"))?;
+                let column = inputs
+                    .next()
+                    .ok_or_else(|| line.missing("a second column"))?;
+                let column = Column::parse(column)
+                    .ok_or_else(|| line.error(column, "unknown second column value"))?;
+                if let Some(rest) = inputs.next() {
+                    return Err(line.error(rest, "unexpected token"));
+                }
+                Ok((opp, column))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part_1(&self) -> usize {
+        self.0
+            .iter()
+            .map(|(opp, column)| {
+                let me = match column {
+                    Column::X => Shape::Rock,
+                    Column::Y => Shape::Paper,
+                    Column::Z => Shape::Scissors,
+                };
+                Game((*opp, me)).score()
+            })
+            .sum()
+    }
+
+    fn part_2(&self) -> usize {
+        self.0
+            .iter()
+            .map(|(opp, column)| {
+                let me = match column {
+                    Column::X => opp.wins_against(),
+                    Column::Y => *opp,
+                    Column::Z => opp.loses_against(),
+                };
+                Game((*opp, me)).score()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StrategyGuide;
+    use crate::examples::{self, examples};
+    use crate::parse::ParseError;
+    use crate::Solution;
+
+    examples!(StrategyGuide, 2);
+
+    #[test]
+    fn test_unknown_shape() {
+        let input = examples::input(2, "example").replace("B X", "D X");
+        assert_eq!(
+            Err(ParseError::new(2, 1, "D", "unknown opponent shape")),
+            StrategyGuide::parse(&input).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_trailing_tokens() {
+        let input = examples::input(2, "example").replace("B X", "B X Q");
+        assert_eq!(
+            Err(ParseError::new(2, 5, "Q", "unexpected token")),
+            StrategyGuide::parse(&input).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::parse::{self, ParseError};
+use crate::Solution;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+fn priority(c: char) -> usize {
+    if c.is_ascii_lowercase() {
+        (c as usize) - 96
+    } else {
+        (c as usize) - 38
+    }
+}
+
+pub struct Rucksacks(Vec<String>);
+
+impl Solution for Rucksacks {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let rucksacks = parse::lines(input)
+            .map(|line| {
+                if let Some((i, c)) = line
+                    .text
+                    .char_indices()
+                    .find(|(_, c)| !c.is_ascii_alphabetic())
+                {
+                    return Err(line.error(&line.text[i..i + c.len_utf8()], "unknown item type"));
+                }
+                if line.text.len() % 2 != 0 {
+                    return Err(line.error(line.text, "expected an even number of items"));
+                }
+                Ok(line.text.into())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if rucksacks.len() % 3 != 0 {
+            let line = parse::lines(input).last().unwrap();
+            return Err(line.error(line.text, "expected rucksacks in groups of three"));
+        }
+        Ok(Self(rucksacks))
+    }
+
+    fn part_1(&self) -> usize {
+        self.0
+            .iter()
+            .map(|l| l.split_at(l.len() / 2))
+            .map(|(fst, snd)| {
+                let map = fst
+                    .chars()
+                    .map(|c| (c, priority(c)))
+                    .collect::<HashMap<_, _>>();
+                for c in snd.chars() {
+                    if let Some(p) = map.get(&c) {
+                        return *p;
+                    }
+                }
+                0usize
+            })
+            .sum()
+    }
+
+    fn part_2(&self) -> usize {
+        self.0
+            .iter()
+            .chunks(3)
+            .into_iter()
+            .map(|mut chunk| {
+                let (fst, snd, thd) = chunk.next_tuple().unwrap();
+                let fst_set = fst.chars().collect::<HashSet<_>>();
+                let thd_map = thd
+                    .chars()
+                    .map(|c| (c, priority(c)))
+                    .collect::<HashMap<_, _>>();
+                for c in snd.chars() {
+                    if fst_set.contains(&c) {
+                        if let Some(p) = thd_map.get(&c) {
+                            return *p;
+                        }
+                    }
+                }
+                0usize
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rucksacks;
+    use crate::examples::examples;
+
+    examples!(Rucksacks, 3);
+}
@@ -0,0 +1,77 @@
+use crate::parse::{self, Line, ParseError};
+use crate::Solution;
+use std::cmp::Ordering;
+
+pub struct SectionAssignment {
+    from: i32,
+    to: i32,
+}
+
+impl SectionAssignment {
+    pub fn parse(line: &Line, input: &str) -> Result<Self, ParseError> {
+        let (from, to) = input
+            .split_once('-')
+            .ok_or_else(|| line.error(input, "expected a section range"))?;
+        Ok(Self {
+            from: line.parse(from)?,
+            to: line.parse(to)?,
+        })
+    }
+
+    pub fn fully_overlaps(&self, other: &SectionAssignment) -> bool {
+        match self.from.cmp(&other.from) {
+            Ordering::Less => matches!(self.to.cmp(&other.to), Ordering::Greater | Ordering::Equal),
+            Ordering::Greater => matches!(self.to.cmp(&other.to), Ordering::Less | Ordering::Equal),
+            Ordering::Equal => true,
+        }
+    }
+
+    pub fn partially_overlaps(&self, other: &SectionAssignment) -> bool {
+        !(self.to < other.from || other.to < self.from)
+    }
+}
+
+pub struct Pairs(Vec<(SectionAssignment, SectionAssignment)>);
+
+impl Solution for Pairs {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse::lines(input)
+            .map(|line| {
+                let (fst, snd) = line
+                    .text
+                    .split_once(',')
+                    .ok_or_else(|| line.error(line.text, "expected a pair of section ranges"))?;
+                Ok((
+                    SectionAssignment::parse(&line, fst)?,
+                    SectionAssignment::parse(&line, snd)?,
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part_1(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|(fst, snd)| fst.fully_overlaps(snd))
+            .count()
+    }
+
+    fn part_2(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|(fst, snd)| fst.partially_overlaps(snd))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Pairs;
+    use crate::examples::examples;
+
+    examples!(Pairs, 4);
+}
@@ -0,0 +1,148 @@
+use crate::parse::{self, Line, ParseError};
+use crate::Solution;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::VecDeque;
+
+static MOVE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap());
+
+pub struct Command {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Command {
+    pub fn parse(line: &Line, stacks: usize) -> Result<Self, ParseError> {
+        let caps = MOVE_REGEX
+            .captures(line.text)
+            .ok_or_else(|| line.error(line.text, "expected a move command"))?;
+        let stack = |i| {
+            let span = caps.get(i).unwrap().as_str();
+            match line.parse(span)? {
+                s if s == 0 || s > stacks => Err(line.error(span, "unknown stack")),
+                s => Ok(s),
+            }
+        };
+        Ok(Self {
+            amount: line.parse(caps.get(1).unwrap().as_str())?,
+            from: stack(2)?,
+            to: stack(3)?,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Crates(Vec<VecDeque<char>>);
+
+impl Crates {
+    pub fn new(lines: &[Line]) -> Result<Self, ParseError> {
+        let mut rev_lines = lines.iter().rev();
+        let first_line = rev_lines
+            .next()
+            .ok_or_else(|| ParseError::new(1, 1, "", "expected a drawing of the stacks"))?;
+        let crates = (first_line.text.len() / 4) + 1;
+        let mut stacks = vec![VecDeque::new(); crates];
+        for line in rev_lines {
+            Self::add_crates(line, &mut stacks)?;
+        }
+
+        Ok(Self(stacks))
+    }
+
+    fn add_crates(line: &Line, stacks: &mut [VecDeque<char>]) -> Result<(), ParseError> {
+        for (i, c) in line.text.char_indices().skip(1).step_by(4) {
+            if c.is_ascii_uppercase() {
+                stacks
+                    .get_mut(i / 4)
+                    .ok_or_else(|| line.error(&line.text[i..=i], "crate outside of the stacks"))?
+                    .push_front(c);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn move_crates_cratemover_9000(&mut self, commands: &[Command]) {
+        for command in commands {
+            for _ in 0..command.amount {
+                if let Some(c) = self.0[command.from - 1].pop_front() {
+                    self.0[command.to - 1].push_front(c);
+                }
+            }
+        }
+    }
+
+    pub fn move_crates_cratemover_9001(&mut self, commands: &[Command]) {
+        for command in commands {
+            let mut v = VecDeque::new();
+            for _ in 0..command.amount {
+                if let Some(c) = self.0[command.from - 1].pop_front() {
+                    v.push_front(c);
+                }
+            }
+            while let Some(c) = v.pop_front() {
+                self.0[command.to - 1].push_front(c);
+            }
+        }
+    }
+
+    pub fn top_crates(&self) -> String {
+        self.0.iter().filter_map(|s| s.front()).collect()
+    }
+}
+
+pub struct Procedure {
+    crates: Crates,
+    commands: Vec<Command>,
+}
+
+impl Solution for Procedure {
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(input);
+        let stacks = lines
+            .by_ref()
+            .take_while(|line| !line.text.is_empty())
+            .collect::<Vec<_>>();
+        let crates = Crates::new(&stacks)?;
+        let commands = lines
+            .map(|line| Command::parse(&line, crates.0.len()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { crates, commands })
+    }
+
+    fn part_1(&self) -> String {
+        let mut crates = self.crates.clone();
+        crates.move_crates_cratemover_9000(&self.commands);
+        crates.top_crates()
+    }
+
+    fn part_2(&self) -> String {
+        let mut crates = self.crates.clone();
+        crates.move_crates_cratemover_9001(&self.commands);
+        crates.top_crates()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Procedure;
+    use crate::examples::{self, examples};
+    use crate::parse::ParseError;
+    use crate::Solution;
+
+    examples!(Procedure, 5);
+
+    #[test]
+    fn test_unknown_stack() {
+        let input =
+            examples::input(5, "example").replace("move 2 from 2 to 1", "move 2 from 2 to 4");
+        assert_eq!(
+            Err(ParseError::new(8, 18, "4", "unknown stack")),
+            Procedure::parse(&input).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::parse::{self, Line, ParseError};
+use crate::Solution;
+use std::collections::HashSet;
+
+const PACKET_MARKER: usize = 4;
+/// A start-of-message marker is longer, so a stream with one also has a start-of-packet marker
+const MESSAGE_MARKER: usize = 14;
+
+pub struct DataStream(Vec<char>);
+
+impl DataStream {
+    /// The number of characters up to the end of the first run of `distinct_chars` different
+    /// characters, if there is one
+    pub fn first_marker(&self, distinct_chars: usize) -> Option<usize> {
+        self.0
+            .windows(distinct_chars)
+            .position(|window| window.iter().collect::<HashSet<_>>().len() == distinct_chars)
+            .map(|start| start + distinct_chars)
+    }
+}
+
+impl From<&str> for DataStream {
+    fn from(input: &str) -> Self {
+        Self(input.chars().collect())
+    }
+}
+
+impl Solution for DataStream {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    /// Parses the stream on the first line, which must contain a start-of-message marker
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let line = parse::lines(input).next().unwrap_or(Line {
+            number: 1,
+            text: "",
+        });
+        let stream = Self::from(line.text);
+        if stream.first_marker(MESSAGE_MARKER).is_none() {
+            return Err(line.missing(&format!(
+                "a marker of {} different characters",
+                MESSAGE_MARKER
+            )));
+        }
+        Ok(stream)
+    }
+
+    fn part_1(&self) -> usize {
+        self.first_marker(PACKET_MARKER).unwrap_or_default()
+    }
+
+    fn part_2(&self) -> usize {
+        self.first_marker(MESSAGE_MARKER).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DataStream;
+    use crate::examples::examples;
+    use crate::parse::ParseError;
+    use crate::Solution;
+
+    examples!(DataStream, 6);
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                5,
+                "",
+                "expected a marker of 14 different characters"
+            )),
+            DataStream::parse("aaaa").map(|_| ())
+        );
+        assert_eq!(Some(5), DataStream::from("ééabcd").first_marker(4));
+    }
+}
@@ -0,0 +1,686 @@
+pub mod json;
+pub mod report;
+pub mod shell;
+pub mod transcript;
+
+use crate::parse::ParseError;
+use crate::Solution;
+use shell::{Shell, Warning};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum FsError {
+    NotFound,
+    NotADir,
+    IsADir,
+    AtRoot,
+    AlreadyExists,
+    InvalidName,
+    /// A total size larger than a `usize`
+    TooLarge,
+    /// Moving a directory into itself
+    IntoItself,
+}
+
+impl Display for FsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FsError::NotFound => write!(f, "no such file or directory"),
+            FsError::NotADir => write!(f, "not a directory"),
+            FsError::IsADir => write!(f, "is a directory"),
+            FsError::AtRoot => write!(f, "already at the root directory"),
+            FsError::AlreadyExists => write!(f, "already exists"),
+            FsError::InvalidName => write!(f, "invalid name"),
+            FsError::TooLarge => write!(f, "total size too large"),
+            FsError::IntoItself => write!(f, "cannot move a directory into itself"),
+        }
+    }
+}
+
+impl Error for FsError {}
+
+enum DirEntry {
+    Dir(Dir),
+    File(File),
+}
+
+impl DirEntry {
+    pub fn name(&self) -> &str {
+        match self {
+            DirEntry::Dir(d) => d.name.as_str(),
+            DirEntry::File(f) => f.name.as_str(),
+        }
+    }
+
+    pub fn parent(&self) -> Option<usize> {
+        match self {
+            DirEntry::Dir(d) => d.parent,
+            DirEntry::File(f) => f.parent,
+        }
+    }
+
+    pub fn dir(&self) -> Option<&Dir> {
+        match self {
+            DirEntry::Dir(d) => Some(d),
+            DirEntry::File(_) => None,
+        }
+    }
+
+    pub fn dir_mut(&mut self) -> Option<&mut Dir> {
+        match self {
+            DirEntry::Dir(d) => Some(d),
+            DirEntry::File(_) => None,
+        }
+    }
+
+    /// The size of a file, or the total size of the files in a directory
+    pub fn size(&self) -> usize {
+        match self {
+            DirEntry::Dir(d) => d.size,
+            DirEntry::File(f) => f.size,
+        }
+    }
+}
+
+struct Dir {
+    name: String,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// The children by name, so finding one along a path does not scan them all
+    names: HashMap<String, usize>,
+    /// The total size of the files in the directory, kept up to date as the tree changes
+    size: usize,
+}
+
+impl Dir {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            parent: None,
+            children: vec![],
+            names: HashMap::new(),
+            size: 0,
+        }
+    }
+}
+
+struct File {
+    name: String,
+    parent: Option<usize>,
+    size: usize,
+}
+
+impl File {
+    fn new(name: &str, size: usize) -> Self {
+        Self {
+            name: name.into(),
+            parent: None,
+            size,
+        }
+    }
+}
+
+/// The size of the disk and the space needed on it, and the size of a directory counted as small
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DiskPolicy {
+    pub disk_size: usize,
+    pub required_space: usize,
+    pub small_dir_limit: usize,
+}
+
+impl DiskPolicy {
+    pub fn free_space(&self, used_space: usize) -> usize {
+        self.disk_size.saturating_sub(used_space)
+    }
+
+    /// How much more space has to be freed to have the required space
+    pub fn space_to_free(&self, used_space: usize) -> usize {
+        self.required_space
+            .saturating_sub(self.free_space(used_space))
+    }
+}
+
+impl Default for DiskPolicy {
+    fn default() -> Self {
+        Self {
+            disk_size: 70_000_000,
+            required_space: 30_000_000,
+            small_dir_limit: 100_000,
+        }
+    }
+}
+
+/// Identifies an entry in a filesystem, for as long as the filesystem exists
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EntryId(usize);
+
+/// A file or directory in a filesystem
+#[derive(Copy, Clone)]
+pub struct Entry<'a> {
+    fs: &'a Filesystem,
+    idx: usize,
+}
+
+impl<'a> Entry<'a> {
+    pub fn id(&self) -> EntryId {
+        EntryId(self.idx)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.fs.entries[self.idx].name()
+    }
+
+    /// The absolute path of the entry
+    pub fn path(&self) -> String {
+        self.fs.path(self.idx)
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.fs.entries[self.idx].dir().is_some()
+    }
+
+    /// The size of a file, or the total size of the files in a directory
+    pub fn size(&self) -> usize {
+        self.fs.size(self.idx)
+    }
+
+    pub fn parent(&self) -> Option<Entry<'a>> {
+        self.fs.entries[self.idx]
+            .parent()
+            .map(|idx| Entry { fs: self.fs, idx })
+    }
+
+    /// The entries in a directory, in the order they were added
+    pub fn children(&self) -> impl Iterator<Item = Entry<'a>> + 'a {
+        let fs = self.fs;
+        fs.entries[self.idx]
+            .dir()
+            .map(|dir| dir.children.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(move |&idx| Entry { fs, idx })
+    }
+}
+
+impl Debug for Entry<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.id(), self.path())
+    }
+}
+
+pub struct Filesystem {
+    entries: Vec<DirEntry>,
+    cwd: usize,
+}
+
+impl Filesystem {
+    /// Rebuilds a filesystem from a terminal transcript
+    pub fn init(input: &str) -> Result<Self, ParseError> {
+        let mut shell = Shell::new(Filesystem::new());
+        shell.replay(input)?;
+        Ok(shell.into_filesystem())
+    }
+
+    /// Rebuilds a filesystem from a terminal transcript, with a warning for every conflict in it
+    pub fn validate(input: &str) -> Result<(Self, Vec<Warning>), ParseError> {
+        let mut shell = Shell::new(Filesystem::new());
+        shell.enable_validation();
+        shell.replay(input)?;
+        let warnings = shell.warnings().unwrap_or_default().to_vec();
+        Ok((shell.into_filesystem(), warnings))
+    }
+
+    pub fn new() -> Self {
+        Self {
+            entries: vec![DirEntry::Dir(Dir::new("/"))],
+            cwd: 0,
+        }
+    }
+
+    /// Adds a directory to the current directory, unless it already has an entry with the name
+    /// or the name is not valid
+    pub fn add_dir(&mut self, name: &str) {
+        self.add_dir_to(self.cwd, name);
+    }
+
+    /// Adds a file to the current directory, unless it already has an entry with the name or
+    /// the name is not valid. Fails if a total size would become too large.
+    pub fn add_file(&mut self, name: &str, size: usize) -> Result<(), FsError> {
+        self.add_file_to(self.cwd, name, size)
+    }
+
+    /// Whether an entry can have a name: not empty, `.` or `..`, without a `/` or a line
+    /// break, and without whitespace at either end, which `cd` would trim off
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains(['/', '\n', '\r'])
+            && name.trim() == name
+    }
+
+    fn add_dir_to(&mut self, dir: usize, name: &str) {
+        if Self::is_valid_name(name) && self.child(dir, name).is_none() {
+            self.insert(dir, DirEntry::Dir(Dir::new(name)))
+                .expect("an empty directory does not change any size");
+        }
+    }
+
+    fn add_file_to(&mut self, dir: usize, name: &str, size: usize) -> Result<(), FsError> {
+        if Self::is_valid_name(name) && self.child(dir, name).is_none() {
+            self.insert(dir, DirEntry::File(File::new(name, size)))?;
+        }
+        Ok(())
+    }
+
+    pub fn cd(&mut self, path: &str) -> Result<(), FsError> {
+        let idx = self.resolve(path)?;
+        self.entries[idx].dir().ok_or(FsError::NotADir)?;
+        self.cwd = idx;
+        Ok(())
+    }
+
+    /// The absolute path of the current directory
+    pub fn pwd(&self) -> String {
+        self.path(self.cwd)
+    }
+
+    pub fn mkdir(&mut self, path: &str) -> Result<(), FsError> {
+        let (parent, name) = self.resolve_new(path)?;
+        self.insert(parent, DirEntry::Dir(Dir::new(name)))
+    }
+
+    /// Creates a file of a size, or changes the size of an existing file
+    pub fn touch(&mut self, path: &str, size: usize) -> Result<(), FsError> {
+        match self.resolve(path) {
+            Ok(idx) => {
+                let DirEntry::File(file) = &self.entries[idx] else {
+                    return Err(FsError::IsADir);
+                };
+                self.resize(file.parent, size, file.size)?;
+                if let DirEntry::File(file) = &mut self.entries[idx] {
+                    file.size = size;
+                }
+                return Ok(());
+            }
+            // A new file cannot be named as a directory
+            Err(FsError::NotFound) if path.ends_with('/') => return Err(FsError::NotADir),
+            Err(FsError::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+        let (parent, name) = self.resolve_new(path)?;
+        self.insert(parent, DirEntry::File(File::new(name, size)))?;
+        Ok(())
+    }
+
+    /// Removes a file, or a directory and everything in it. Removing the current directory
+    /// moves to its parent.
+    pub fn rm(&mut self, path: &str) -> Result<(), FsError> {
+        let idx = self.resolve(path)?;
+        let parent = self.entries[idx].parent().ok_or(FsError::AtRoot)?;
+        if self.is_within(self.cwd, idx) {
+            self.cwd = parent;
+        }
+        self.detach(idx);
+        Ok(())
+    }
+
+    /// Moves an entry into a directory, or renames it if the destination does not exist
+    pub fn mv(&mut self, from: &str, to: &str) -> Result<(), FsError> {
+        let idx = self.resolve(from)?;
+        if self.entries[idx].parent().is_none() {
+            return Err(FsError::AtRoot);
+        }
+        let (parent, name) = match self.resolve(to) {
+            Ok(dest) if self.entries[dest].dir().is_some() => {
+                (dest, self.entries[idx].name().to_string())
+            }
+            Ok(_) => return Err(FsError::AlreadyExists),
+            Err(FsError::NotFound) => {
+                let (parent, name) = self.resolve_new(to)?;
+                (parent, name.to_string())
+            }
+            Err(e) => return Err(e),
+        };
+        if self.child(parent, &name).is_some() {
+            return Err(FsError::AlreadyExists);
+        }
+        if self.is_within(parent, idx) {
+            return Err(FsError::IntoItself);
+        }
+
+        self.detach(idx);
+        match &mut self.entries[idx] {
+            DirEntry::Dir(d) => d.name = name,
+            DirEntry::File(f) => f.name = name,
+        }
+        self.attach(parent, idx)?;
+        Ok(())
+    }
+
+    /// The absolute path of an entry
+    fn path(&self, idx: usize) -> String {
+        let mut names = vec![];
+        let mut entry = &self.entries[idx];
+        while let Some(parent) = entry.parent() {
+            names.push(entry.name());
+            entry = &self.entries[parent];
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Finds the entry at an absolute path, or one relative to the current directory. A path
+    /// ending in `/` must be a directory.
+    fn resolve(&self, path: &str) -> Result<usize, FsError> {
+        let mut idx = if path.starts_with('/') { 0 } else { self.cwd };
+        for name in path.split('/').filter(|n| !n.is_empty() && *n != ".") {
+            idx = match name {
+                ".." => self.entries[idx].parent().ok_or(FsError::AtRoot)?,
+                _ => {
+                    self.entries[idx].dir().ok_or(FsError::NotADir)?;
+                    self.child(idx, name).ok_or(FsError::NotFound)?
+                }
+            };
+        }
+        if path.ends_with('/') && self.entries[idx].dir().is_none() {
+            return Err(FsError::NotADir);
+        }
+        Ok(idx)
+    }
+
+    /// Finds the directory a new entry would be created in, and the new entry's name
+    fn resolve_new<'p>(&self, path: &'p str) -> Result<(usize, &'p str), FsError> {
+        let path = path.trim_end_matches('/');
+        let (dir, name) = match path.rsplit_once('/') {
+            Some(("", name)) => (0, name),
+            Some((dir, name)) => (self.resolve(dir)?, name),
+            None => (self.cwd, path),
+        };
+        if !Self::is_valid_name(name) {
+            return Err(FsError::InvalidName);
+        }
+        self.entries[dir].dir().ok_or(FsError::NotADir)?;
+        if self.child(dir, name).is_some() {
+            return Err(FsError::AlreadyExists);
+        }
+        Ok((dir, name))
+    }
+
+    fn child(&self, dir: usize, name: &str) -> Option<usize> {
+        self.entries[dir].dir()?.names.get(name).copied()
+    }
+
+    /// Whether an entry is the given directory or inside it
+    fn is_within(&self, idx: usize, dir: usize) -> bool {
+        std::iter::successors(Some(idx), |i| self.entries[*i].parent()).any(|i| i == dir)
+    }
+
+    fn insert(&mut self, parent: usize, entry: DirEntry) -> Result<(), FsError> {
+        self.resize(Some(parent), entry.size(), 0)?;
+        let idx = self.entries.len();
+        self.entries.push(entry);
+        self.link(parent, idx);
+        Ok(())
+    }
+
+    fn attach(&mut self, parent: usize, idx: usize) -> Result<(), FsError> {
+        self.resize(Some(parent), self.entries[idx].size(), 0)?;
+        self.link(parent, idx);
+        Ok(())
+    }
+
+    fn link(&mut self, parent: usize, idx: usize) {
+        match &mut self.entries[idx] {
+            DirEntry::Dir(d) => d.parent = Some(parent),
+            DirEntry::File(f) => f.parent = Some(parent),
+        }
+        let name = self.entries[idx].name().to_string();
+        let dir = self.entries[parent].dir_mut().unwrap();
+        dir.children.push(idx);
+        dir.names.insert(name, idx);
+    }
+
+    /// Unlinks an entry from its parent. It stays in `entries` so that ids stay stable, but
+    /// can no longer be reached from the root.
+    fn detach(&mut self, idx: usize) {
+        if let Some(parent) = self.entries[idx].parent() {
+            let name = self.entries[idx].name().to_string();
+            let dir = self.entries[parent].dir_mut().unwrap();
+            dir.children.retain(|c| *c != idx);
+            dir.names.remove(&name);
+            self.resize(Some(parent), 0, self.entries[idx].size())
+                .expect("removing an entry does not grow any size");
+        }
+        match &mut self.entries[idx] {
+            DirEntry::Dir(d) => d.parent = None,
+            DirEntry::File(f) => f.parent = None,
+        }
+    }
+
+    /// Adds to and removes from the size of a directory and every directory above it, or
+    /// changes nothing if a size would become too large
+    fn resize(&mut self, dir: Option<usize>, added: usize, removed: usize) -> Result<(), FsError> {
+        let dirs = std::iter::successors(dir, |i| self.entries[*i].parent()).collect::<Vec<_>>();
+        // The topmost directory is the largest, so if it has room then so do the others
+        if let Some(&top) = dirs.last() {
+            (self.entries[top].size() - removed)
+                .checked_add(added)
+                .ok_or(FsError::TooLarge)?;
+        }
+        for idx in dirs {
+            let d = self.entries[idx].dir_mut().unwrap();
+            d.size = d.size - removed + added;
+        }
+        Ok(())
+    }
+
+    pub fn root(&self) -> Entry<'_> {
+        Entry { fs: self, idx: 0 }
+    }
+
+    /// The entry with an id, unless it has been removed
+    pub fn entry(&self, id: EntryId) -> Option<Entry<'_>> {
+        (id.0 < self.entries.len() && self.is_within(id.0, 0)).then_some(Entry {
+            fs: self,
+            idx: id.0,
+        })
+    }
+
+    /// The entry at an absolute path, or one relative to the current directory
+    pub fn get(&self, path: &str) -> Option<Entry<'_>> {
+        self.resolve(path).ok().map(|idx| Entry { fs: self, idx })
+    }
+
+    /// The size of every directory, by id
+    pub fn dir_sizes(&self) -> BTreeMap<EntryId, usize> {
+        let mut dirs = BTreeMap::new();
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            if let DirEntry::Dir(dir) = &self.entries[idx] {
+                dirs.insert(EntryId(idx), dir.size);
+                stack.extend(&dir.children);
+            }
+        }
+        dirs
+    }
+
+    /// The total size of every file
+    pub fn used_space(&self) -> usize {
+        self.size(0)
+    }
+
+    /// Every directory with a total size of at most `size`, by id
+    pub fn dirs_at_most(&self, size: usize) -> Vec<Entry<'_>> {
+        self.dir_sizes()
+            .into_iter()
+            .filter(|(_, s)| *s <= size)
+            .map(|(id, _)| Entry {
+                fs: self,
+                idx: id.0,
+            })
+            .collect()
+    }
+
+    /// The smallest directory that would free at least `size` if it were deleted
+    pub fn smallest_dir_freeing(&self, size: usize) -> Option<Entry<'_>> {
+        self.dir_sizes()
+            .into_iter()
+            .filter(|(_, s)| *s >= size)
+            .min_by_key(|(id, s)| (*s, *id))
+            .map(|(id, _)| Entry {
+                fs: self,
+                idx: id.0,
+            })
+    }
+
+    /// The `k` largest directories, largest first
+    pub fn largest_dirs(&self, k: usize) -> Vec<Entry<'_>> {
+        let mut dirs = self.dir_sizes().into_iter().collect::<Vec<_>>();
+        dirs.sort_by_key(|(id, s)| (Reverse(*s), *id));
+        dirs.into_iter()
+            .take(k)
+            .map(|(id, _)| Entry {
+                fs: self,
+                idx: id.0,
+            })
+            .collect()
+    }
+
+    /// The total size of the directories that are small under a policy
+    pub fn total_size(&self, policy: &DiskPolicy) -> usize {
+        self.dir_sizes()
+            .into_values()
+            .filter(|size| *size <= policy.small_dir_limit)
+            .sum()
+    }
+
+    /// The smallest directory that would free enough space under a policy if it were deleted
+    pub fn smallest_dir(&self, policy: &DiskPolicy) -> Option<Entry<'_>> {
+        self.smallest_dir_freeing(policy.space_to_free(self.used_space()))
+    }
+
+    fn size(&self, idx: usize) -> usize {
+        self.entries[idx].size()
+    }
+}
+
+impl Default for Filesystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solution for Filesystem {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::init(input)
+    }
+
+    fn part_1(&self) -> usize {
+        self.total_size(&DiskPolicy::default())
+    }
+
+    fn part_2(&self) -> usize {
+        self.smallest_dir(&DiskPolicy::default())
+            .map(|dir| dir.size())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DiskPolicy, Entry, Filesystem, FsError};
+    use crate::examples::{self, examples};
+    use crate::Solution;
+
+    examples!(Filesystem, 7);
+
+    #[test]
+    fn test_queries() {
+        let fs = Filesystem::parse(&examples::input(7, "example")).unwrap();
+        let paths = |dirs: Vec<Entry>| {
+            dirs.iter()
+                .map(|d| (d.path(), d.size()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(48381165, fs.used_space());
+        assert_eq!(
+            vec![("/a/e".to_string(), 584)],
+            paths(fs.dirs_at_most(1000))
+        );
+        assert_eq!(
+            vec![("/a".to_string(), 94853)],
+            paths(fs.smallest_dir_freeing(1000).into_iter().collect())
+        );
+        assert_eq!(
+            vec![("/".to_string(), 48381165), ("/d".to_string(), 24933642)],
+            paths(fs.largest_dirs(2))
+        );
+
+        let policy = DiskPolicy {
+            disk_size: 50_000_000,
+            ..DiskPolicy::default()
+        };
+        assert_eq!(28381165, policy.space_to_free(fs.used_space()));
+        assert_eq!(fs.root().id(), fs.smallest_dir(&policy).unwrap().id());
+    }
+
+    #[test]
+    fn test_entries() {
+        let mut fs = Filesystem::parse(&examples::input(7, "example")).unwrap();
+        let e = fs.get("/a/e").unwrap();
+        assert_eq!(("e", "/a/e", 584), (e.name(), e.path().as_str(), e.size()));
+        assert_eq!(Some("/a".to_string()), e.parent().map(|p| p.path()));
+        assert_eq!(e.id(), fs.get("../a/./e").unwrap().id());
+        assert_eq!(
+            vec!["i"],
+            e.children().map(|c| c.name()).collect::<Vec<_>>()
+        );
+        assert!(fs.get("/a/x").is_none());
+
+        // Directories with the same size are still distinct
+        fs.mkdir("/x").unwrap();
+        fs.mkdir("/y").unwrap();
+        let sizes = fs.dir_sizes();
+        assert_eq!(6, sizes.len());
+        assert_eq!(2, sizes.values().filter(|size| **size == 0).count());
+
+        let id = fs.get("/x").unwrap().id();
+        fs.rm("/x").unwrap();
+        assert!(fs.entry(id).is_none());
+        assert_eq!(
+            Some("/y".to_string()),
+            fs.entry(fs.get("/y").unwrap().id()).map(|y| y.path())
+        );
+    }
+
+    #[test]
+    fn test_sizes_follow_changes() {
+        let mut fs = Filesystem::parse(&examples::input(7, "example")).unwrap();
+        let size = |fs: &Filesystem, path| fs.get(path).unwrap().size();
+        fs.touch("/a/e/i", 1000).unwrap();
+        assert_eq!(
+            (1000, 95269, 48381581),
+            (size(&fs, "/a/e"), size(&fs, "/a"), size(&fs, "/"))
+        );
+
+        fs.mv("/a/e", "/d/e").unwrap();
+        assert_eq!((94269, 24934642), (size(&fs, "/a"), size(&fs, "/d")));
+        fs.rm("/d").unwrap();
+        fs.cd("/a").unwrap();
+        fs.add_file("z", 1).unwrap();
+        assert_eq!((94270, 23446940), (size(&fs, "/a"), fs.used_space()));
+
+        assert_eq!(Err(FsError::TooLarge), fs.touch("/a/z", usize::MAX));
+        assert_eq!(Err(FsError::TooLarge), fs.touch("/b.txt", usize::MAX));
+        assert_eq!(Err(FsError::TooLarge), fs.add_file("y", usize::MAX));
+        assert_eq!((94270, 23446940), (size(&fs, "/a"), fs.used_space()));
+        assert!(fs.get("/a/y").is_none());
+    }
+}
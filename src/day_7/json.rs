@@ -0,0 +1,241 @@
+use super::{Dir, DirEntry, File, Filesystem};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A filesystem as a JSON document: every entry that can be reached from the root, numbered
+/// from the root down in the order they were listed, and the current directory
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    cwd: usize,
+    entries: Vec<Node>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Node {
+    Dir {
+        id: usize,
+        name: String,
+        parent: Option<usize>,
+        /// The total size of the files in the directory
+        size: usize,
+        children: Vec<usize>,
+    },
+    File {
+        id: usize,
+        name: String,
+        parent: Option<usize>,
+        size: usize,
+    },
+}
+
+impl Node {
+    fn id(&self) -> usize {
+        match self {
+            Node::Dir { id, .. } | Node::File { id, .. } => *id,
+        }
+    }
+
+    fn parent(&self) -> Option<usize> {
+        match self {
+            Node::Dir { parent, .. } | Node::File { parent, .. } => *parent,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Node::Dir { size, .. } | Node::File { size, .. } => *size,
+        }
+    }
+}
+
+impl Serialize for Filesystem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Number the entries in the order a walk from the root reaches them, so that
+        // filesystems with the same tree have the same document however they were built
+        let mut order = vec![];
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            order.push(idx);
+            if let Some(dir) = self.entries[idx].dir() {
+                stack.extend(dir.children.iter().rev());
+            }
+        }
+        let mut ids = vec![None; self.entries.len()];
+        for (id, &idx) in order.iter().enumerate() {
+            ids[idx] = Some(id);
+        }
+        let id = |idx: usize| ids[idx].unwrap();
+
+        let entries = order
+            .iter()
+            .map(|&idx| match &self.entries[idx] {
+                DirEntry::Dir(d) => Node::Dir {
+                    id: id(idx),
+                    name: d.name.clone(),
+                    parent: d.parent.map(id),
+                    size: d.size,
+                    children: d.children.iter().map(|c| id(*c)).collect(),
+                },
+                DirEntry::File(f) => Node::File {
+                    id: id(idx),
+                    name: f.name.clone(),
+                    parent: f.parent.map(id),
+                    size: f.size,
+                },
+            })
+            .collect();
+        Snapshot {
+            cwd: id(self.cwd),
+            entries,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Filesystem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let snapshot = Snapshot::deserialize(deserializer)?;
+        Filesystem::try_from(snapshot).map_err(D::Error::custom)
+    }
+}
+
+impl TryFrom<Snapshot> for Filesystem {
+    type Error = String;
+
+    /// Rebuilds the tree by adding each entry to its parent in turn, then checks that the
+    /// children and sizes in the document agree with it
+    fn try_from(snapshot: Snapshot) -> Result<Self, Self::Error> {
+        let mut fs = Filesystem::new();
+        for (i, node) in snapshot.entries.iter().enumerate() {
+            if node.id() != i {
+                return Err(format!("entry {} has id {}", i, node.id()));
+            }
+            let parent = match (i, node.parent()) {
+                (0, None) => continue,
+                (0, Some(_)) => return Err("the root has a parent".into()),
+                (_, None) => return Err(format!("entry {} has no parent", i)),
+                (_, Some(parent)) if parent >= i => {
+                    return Err(format!("entry {} comes before its parent {}", i, parent))
+                }
+                (_, Some(parent)) => parent,
+            };
+            let (entry, name) = match node {
+                Node::Dir { name, .. } => (DirEntry::Dir(Dir::new(name)), name),
+                Node::File { name, size, .. } => (DirEntry::File(File::new(name, *size)), name),
+            };
+            if fs.entries[parent].dir().is_none() {
+                return Err(format!("the parent of entry {} is a file", i));
+            }
+            if !Filesystem::is_valid_name(name) {
+                return Err(format!("entry {} has an invalid name {:?}", i, name));
+            }
+            if fs.child(parent, name).is_some() {
+                return Err(format!("entry {} has the same name as another entry", i));
+            }
+            fs.insert(parent, entry)
+                .map_err(|e| format!("entry {}: {}", i, e))?;
+        }
+
+        match snapshot.entries.first() {
+            Some(Node::Dir { .. }) => {}
+            _ => return Err("the root is not a directory".into()),
+        }
+        for (i, node) in snapshot.entries.iter().enumerate() {
+            if let Node::Dir { children, .. } = node {
+                if *children != fs.entries[i].dir().unwrap().children {
+                    return Err(format!("the children of entry {} do not match", i));
+                }
+            }
+            if node.size() != fs.entries[i].size() {
+                return Err(format!("the size of entry {} does not match", i));
+            }
+        }
+        match fs.entries.get(snapshot.cwd) {
+            Some(DirEntry::Dir(_)) => fs.cwd = snapshot.cwd,
+            _ => {
+                return Err(format!(
+                    "the current directory {} is not a directory",
+                    snapshot.cwd
+                ))
+            }
+        }
+        Ok(fs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day_7::Filesystem;
+    use crate::examples;
+
+    #[test]
+    fn test_round_trip() {
+        let fs = Filesystem::init(&examples::input(7, "example")).unwrap();
+        let json = serde_json::to_value(&fs).unwrap();
+        assert_eq!(
+            serde_json::json!({"type": "dir", "id": 1, "name": "a", "parent": 0, "size": 94853, "children": [2, 4, 5, 6]}),
+            json["entries"][1]
+        );
+        assert_eq!(
+            serde_json::json!({"type": "file", "id": 3, "name": "i", "parent": 2, "size": 584}),
+            json["entries"][3]
+        );
+
+        let read = serde_json::from_value::<Filesystem>(json.clone()).unwrap();
+        assert_eq!("/d", read.pwd());
+        assert_eq!(fs.tree(None, false), read.tree(None, false));
+        assert_eq!(json, serde_json::to_value(&read).unwrap());
+    }
+
+    #[test]
+    fn test_same_tree_same_snapshot() {
+        let mut fs = Filesystem::init(&examples::input(7, "example")).unwrap();
+        fs.mkdir("/x").unwrap();
+        fs.rm("/x").unwrap();
+        assert_eq!(
+            serde_json::to_string(&Filesystem::init(&examples::input(7, "example")).unwrap())
+                .unwrap(),
+            serde_json::to_string(&fs).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let read = |json: &str| {
+            serde_json::from_str::<Filesystem>(json)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        };
+        let root =
+            r#"{"type": "dir", "id": 0, "name": "/", "parent": null, "size": 5, "children": [1]}"#;
+        assert_eq!(
+            Ok(()),
+            read(&format!(
+                r#"{{"cwd": 0, "entries": [{}, {{"type": "file", "id": 1, "name": "f", "parent": 0, "size": 5}}]}}"#,
+                root
+            ))
+        );
+        assert_eq!(
+            Err("the size of entry 0 does not match".into()),
+            read(&format!(
+                r#"{{"cwd": 0, "entries": [{}, {{"type": "file", "id": 1, "name": "f", "parent": 0, "size": 4}}]}}"#,
+                root
+            ))
+        );
+        assert_eq!(
+            Err("the current directory 1 is not a directory".into()),
+            read(&format!(
+                r#"{{"cwd": 1, "entries": [{}, {{"type": "file", "id": 1, "name": "f", "parent": 0, "size": 5}}]}}"#,
+                root
+            ))
+        );
+        assert_eq!(
+            Err("entry 1 comes before its parent 1".into()),
+            read(&format!(
+                r#"{{"cwd": 0, "entries": [{}, {{"type": "file", "id": 1, "name": "f", "parent": 1, "size": 5}}]}}"#,
+                root
+            ))
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::{DirEntry, Filesystem};
+use std::fmt::Write;
+
+const UNITS: [&str; 5] = ["", "K", "M", "G", "T"];
+
+/// A size in powers of 1024 as `du -h` shows it, rounded up to one decimal place below 10
+/// and to a whole number above
+pub fn human(size: usize) -> String {
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        return size.to_string();
+    }
+    let tenths = (value * 10.0).ceil() / 10.0;
+    if tenths < 10.0 {
+        format!("{:.1}{}", tenths, UNITS[unit])
+    } else {
+        format!("{}{}", value.ceil(), UNITS[unit])
+    }
+}
+
+/// The sizes of a filesystem's entries, as they are shown in a report
+struct Sizes<'a> {
+    fs: &'a Filesystem,
+    human_readable: bool,
+}
+
+impl Sizes<'_> {
+    fn show(&self, idx: usize) -> String {
+        if self.human_readable {
+            human(self.fs.size(idx))
+        } else {
+            self.fs.size(idx).to_string()
+        }
+    }
+}
+
+impl Filesystem {
+    /// An indented listing of the tree with the size of every entry, in the order they were
+    /// listed, like `tree`. Directories more than `depth` below the root are left out.
+    pub fn tree(&self, depth: Option<usize>, human_readable: bool) -> String {
+        let sizes = Sizes {
+            fs: self,
+            human_readable,
+        };
+        let mut tree = format!("/ ({})\n", sizes.show(0));
+        self.write_tree(0, "", depth, &sizes, &mut tree);
+        tree
+    }
+
+    fn write_tree(
+        &self,
+        idx: usize,
+        prefix: &str,
+        depth: Option<usize>,
+        sizes: &Sizes<'_>,
+        tree: &mut String,
+    ) {
+        let Some(dir) = self.entries[idx].dir() else {
+            return;
+        };
+        if depth == Some(0) {
+            return;
+        }
+        for (i, &child) in dir.children.iter().enumerate() {
+            let last = i + 1 == dir.children.len();
+            let entry = &self.entries[child];
+            writeln!(
+                tree,
+                "{}{} {}{} ({})",
+                prefix,
+                if last { "└──" } else { "├──" },
+                entry.name(),
+                if entry.dir().is_some() { "/" } else { "" },
+                sizes.show(child)
+            )
+            .unwrap();
+            let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            self.write_tree(child, &prefix, depth.map(|d| d - 1), sizes, tree);
+        }
+    }
+
+    /// The size and path of every directory, largest first, like `du`. Only directories at
+    /// most `depth` below the root are included.
+    pub fn du(&self, depth: Option<usize>, human_readable: bool) -> String {
+        let sizes = Sizes {
+            fs: self,
+            human_readable,
+        };
+        let mut dirs = vec![];
+        let mut stack = vec![(0, 0)];
+        while let Some((idx, level)) = stack.pop() {
+            let DirEntry::Dir(dir) = &self.entries[idx] else {
+                continue;
+            };
+            if depth.is_some_and(|d| level > d) {
+                continue;
+            }
+            dirs.push((idx, self.path(idx)));
+            stack.extend(dir.children.iter().map(|c| (*c, level + 1)));
+        }
+        dirs.sort_by(|(a, a_path), (b, b_path)| {
+            (self.size(*b).cmp(&self.size(*a))).then_with(|| a_path.cmp(b_path))
+        });
+
+        let mut du = String::new();
+        for (idx, path) in dirs {
+            writeln!(&mut du, "{}\t{}", sizes.show(idx), path).unwrap();
+        }
+        du
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::human;
+    use crate::day_7::Filesystem;
+    use crate::examples;
+
+    fn filesystem() -> Filesystem {
+        Filesystem::init(&examples::input(7, "example")).unwrap()
+    }
+
+    #[test]
+    fn test_tree() {
+        assert_eq!(
+            "/ (48381165)\n\
+             ├── a/ (94853)\n\
+             │   ├── e/ (584)\n\
+             │   ├── f (29116)\n\
+             │   ├── g (2557)\n\
+             │   └── h.lst (62596)\n\
+             ├── b.txt (14848514)\n\
+             ├── c.dat (8504156)\n\
+             └── d/ (24933642)\n\
+             \u{20}   ├── j (4060174)\n\
+             \u{20}   ├── d.log (8033020)\n\
+             \u{20}   ├── d.ext (5626152)\n\
+             \u{20}   └── k (7214296)\n",
+            filesystem().tree(Some(2), false)
+        );
+    }
+
+    #[test]
+    fn test_du() {
+        assert_eq!(
+            "47M\t/\n24M\t/d\n93K\t/a\n584\t/a/e\n",
+            filesystem().du(None, true)
+        );
+        assert_eq!("48381165\t/\n", filesystem().du(Some(0), false));
+    }
+
+    #[test]
+    fn test_human() {
+        assert_eq!("1023", human(1023));
+        assert_eq!("1.0K", human(1024));
+        assert_eq!("1.1K", human(1025));
+        assert_eq!("10K", human(10 * 1024 - 1));
+        assert_eq!("1.0G", human(1 << 30));
+    }
+}
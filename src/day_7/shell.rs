@@ -0,0 +1,485 @@
+use super::{DirEntry, Filesystem, FsError};
+use crate::parse::{self, Line, ParseError};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt::{Display, Formatter, Write};
+
+static FILE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d+) (.*)$").unwrap());
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command<'a> {
+    Cd(&'a str),
+    /// Lists a directory, or the current directory
+    Ls(Option<&'a str>),
+    Pwd,
+    Mkdir(&'a str),
+    /// Creates a file of a size
+    Touch(&'a str, usize),
+    Rm(&'a str),
+    Mv(&'a str, &'a str),
+}
+
+impl<'a> Command<'a> {
+    /// Parses a command from a span of a line, without the `$` prompt
+    pub fn parse(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        let mut words = text.split_whitespace();
+        let name = words.next().ok_or_else(|| line.missing("a command"))?;
+        let args = words.collect::<Vec<_>>();
+        // `cd` and `ls` take the rest of the line, so they work with names containing spaces
+        let rest = text.trim_start()[name.len()..].trim();
+        let command = match (name, args.as_slice()) {
+            ("cd", []) => Command::Cd("/"),
+            ("cd", _) => Command::Cd(rest),
+            ("ls", []) => Command::Ls(None),
+            ("ls", _) => Command::Ls(Some(rest)),
+            ("pwd", []) => Command::Pwd,
+            ("mkdir", [path]) => Command::Mkdir(path),
+            ("touch", [path, size]) => Command::Touch(path, line.parse(size)?),
+            ("rm", [path]) => Command::Rm(path),
+            ("mv", [from, to]) => Command::Mv(from, to),
+            ("pwd" | "mkdir" | "touch" | "rm" | "mv", _) => {
+                return Err(line.error(text, format!("usage: {}", usage(name))))
+            }
+            _ => return Err(line.error(name, "unknown command")),
+        };
+        Ok(command)
+    }
+
+    /// The path the command acts on, to point at when it fails
+    fn path(&self) -> Option<&'a str> {
+        match *self {
+            Command::Cd(path)
+            | Command::Ls(Some(path))
+            | Command::Mkdir(path)
+            | Command::Touch(path, _)
+            | Command::Rm(path)
+            | Command::Mv(path, _) => Some(path),
+            Command::Ls(None) | Command::Pwd => None,
+        }
+    }
+}
+
+fn usage(command: &str) -> &'static str {
+    match command {
+        "pwd" => "pwd",
+        "mkdir" => "mkdir <path>",
+        "touch" => "touch <path> <size>",
+        "rm" => "rm <path>",
+        _ => "mv <from> <to>",
+    }
+}
+
+/// Something in a transcript that disagrees with what came before it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Conflict {
+    /// A file listed again with a different size
+    SizeChanged {
+        path: String,
+        size: usize,
+        listed: usize,
+    },
+    /// A directory listed again as a file
+    DirListedAsFile(String),
+    /// A file listed again as a directory
+    FileListedAsDir(String),
+    /// `cd ..` in the root directory
+    AboveRoot,
+    /// `cd` into a directory that no listing included
+    Unlisted(String),
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::SizeChanged { path, size, listed } => write!(
+                f,
+                "{} was listed with size {}, then with size {}",
+                path, size, listed
+            ),
+            Conflict::DirListedAsFile(path) => {
+                write!(f, "{} was listed as a directory, then as a file", path)
+            }
+            Conflict::FileListedAsDir(path) => {
+                write!(f, "{} was listed as a file, then as a directory", path)
+            }
+            Conflict::AboveRoot => write!(f, "cd .. in the root directory"),
+            Conflict::Unlisted(path) => write!(f, "cd into {}, which was never listed", path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Warning {
+    pub line: usize,
+    pub conflict: Conflict,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.conflict)
+    }
+}
+
+/// What the lines following a command in a transcript are
+enum Output {
+    /// The entries of a directory
+    Listing(usize),
+    /// Output of a command that does not change the filesystem
+    Ignored,
+    None,
+}
+
+/// Runs commands against a filesystem, typed interactively or replayed from a transcript
+pub struct Shell {
+    fs: Filesystem,
+    output: Output,
+    warnings: Option<Vec<Warning>>,
+}
+
+impl Shell {
+    pub fn new(fs: Filesystem) -> Self {
+        Self {
+            fs,
+            output: Output::None,
+            warnings: None,
+        }
+    }
+
+    /// Makes replays record conflicts in the transcript as warnings, and carry on past `cd ..`
+    /// in the root and `cd` into directories that were never listed
+    pub fn enable_validation(&mut self) {
+        self.warnings.get_or_insert_with(Vec::new);
+    }
+
+    /// The conflicts found by replays since validation was enabled
+    pub fn warnings(&self) -> Option<&[Warning]> {
+        self.warnings.as_deref()
+    }
+
+    pub fn filesystem(&self) -> &Filesystem {
+        &self.fs
+    }
+
+    pub fn into_filesystem(self) -> Filesystem {
+        self.fs
+    }
+
+    /// Runs a command, returning what it prints
+    pub fn execute(&mut self, command: &Command) -> Result<String, FsError> {
+        match *command {
+            Command::Cd(path) => self.fs.cd(path).map(|()| String::new()),
+            Command::Ls(path) => {
+                let idx = path.map_or(Ok(self.fs.cwd), |p| self.fs.resolve(p))?;
+                Ok(self.listing(idx))
+            }
+            Command::Pwd => Ok(self.fs.pwd()),
+            Command::Mkdir(path) => self.fs.mkdir(path).map(|()| String::new()),
+            Command::Touch(path, size) => self.fs.touch(path, size).map(|()| String::new()),
+            Command::Rm(path) => self.fs.rm(path).map(|()| String::new()),
+            Command::Mv(from, to) => self.fs.mv(from, to).map(|()| String::new()),
+        }
+    }
+
+    /// Runs a line typed at the prompt, with or without the `$`, returning what it prints
+    pub fn run(&mut self, text: &str) -> Result<String, String> {
+        let line = Line { number: 1, text };
+        let command = Command::parse(&line, text.trim_start_matches('$'))
+            .map_err(|e| format!("{}: {:?}", e.reason(), e.text()))?;
+        self.execute(&command).map_err(|e| match command.path() {
+            Some(path) => format!("{}: {}", path, e),
+            None => e.to_string(),
+        })
+    }
+
+    /// Replays a transcript of commands and their output, adding the entries listed by `ls`
+    pub fn replay(&mut self, input: &str) -> Result<(), ParseError> {
+        for line in parse::lines(input) {
+            if let Some(text) = line.text.strip_prefix('$') {
+                let command = Command::parse(&line, text)?;
+                self.output = match command {
+                    Command::Ls(None) => Output::Listing(self.fs.cwd),
+                    Command::Ls(Some(path)) => {
+                        let idx = self
+                            .fs
+                            .resolve(path)
+                            .and_then(|idx| match self.fs.entries[idx] {
+                                DirEntry::Dir(_) => Ok(idx),
+                                DirEntry::File(_) => Err(FsError::NotADir),
+                            })
+                            .map_err(|e| line.error(path, e.to_string()))?;
+                        Output::Listing(idx)
+                    }
+                    Command::Pwd => Output::Ignored,
+                    Command::Cd(path) if self.warnings.is_some() => {
+                        self.cd_checked(line.number, path)
+                            .map_err(|e| line.error(path, e.to_string()))?;
+                        Output::None
+                    }
+                    _ => {
+                        self.execute(&command).map_err(|e| {
+                            line.error(command.path().unwrap_or(text), e.to_string())
+                        })?;
+                        Output::None
+                    }
+                };
+                continue;
+            }
+
+            let dir = match self.output {
+                Output::Listing(dir) => dir,
+                Output::Ignored => continue,
+                Output::None => return Err(line.error(line.text, "unexpected output")),
+            };
+            if let Some(name) = line.text.strip_prefix("dir ") {
+                if !Filesystem::is_valid_name(name) {
+                    return Err(line.error(name, FsError::InvalidName.to_string()));
+                }
+                self.check_listed(line.number, dir, name, None);
+                self.fs.add_dir_to(dir, name);
+            } else {
+                let caps = FILE_REGEX
+                    .captures(line.text)
+                    .ok_or_else(|| line.error(line.text, "expected a directory or file"))?;
+                let (name, size) = (
+                    caps.get(2).unwrap().as_str(),
+                    line.parse(caps.get(1).unwrap().as_str())?,
+                );
+                if !Filesystem::is_valid_name(name) {
+                    return Err(line.error(name, FsError::InvalidName.to_string()));
+                }
+                self.check_listed(line.number, dir, name, Some(size));
+                self.fs
+                    .add_file_to(dir, name, size)
+                    .map_err(|e| line.error(line.text, e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a conflict if a listed entry, a file when it has a size, disagrees with the
+    /// entry already in the directory
+    fn check_listed(&mut self, line: usize, dir: usize, name: &str, size: Option<usize>) {
+        let Some(warnings) = &mut self.warnings else {
+            return;
+        };
+        let Some(idx) = self.fs.child(dir, name) else {
+            return;
+        };
+        let path = self.fs.path(idx);
+        let conflict = match (&self.fs.entries[idx], size) {
+            (DirEntry::File(f), Some(listed)) if f.size != listed => Conflict::SizeChanged {
+                path,
+                size: f.size,
+                listed,
+            },
+            (DirEntry::Dir(_), Some(_)) => Conflict::DirListedAsFile(path),
+            (DirEntry::File(_), None) => Conflict::FileListedAsDir(path),
+            _ => return,
+        };
+        warnings.push(Warning { line, conflict });
+    }
+
+    /// Changes directory one name at a time, staying in the root on `cd ..` and creating
+    /// directories that were never listed, with a warning for each
+    fn cd_checked(&mut self, line: usize, path: &str) -> Result<(), FsError> {
+        let cwd = self.fs.cwd;
+        let mut conflicts = vec![];
+        if let Err(e) = self.walk(path, &mut conflicts) {
+            self.fs.cwd = cwd;
+            return Err(e);
+        }
+        let warnings = self.warnings.as_mut().unwrap();
+        warnings.extend(
+            conflicts
+                .into_iter()
+                .map(|conflict| Warning { line, conflict }),
+        );
+        Ok(())
+    }
+
+    fn walk(&mut self, path: &str, conflicts: &mut Vec<Conflict>) -> Result<(), FsError> {
+        if path.starts_with('/') {
+            self.fs.cwd = 0;
+        }
+        for name in path.split('/').filter(|n| !n.is_empty() && *n != ".") {
+            match self.fs.cd(name) {
+                Err(FsError::AtRoot) => conflicts.push(Conflict::AboveRoot),
+                Err(FsError::NotFound) => {
+                    self.fs.mkdir(name)?;
+                    self.fs.cd(name)?;
+                    conflicts.push(Conflict::Unlisted(self.fs.pwd()));
+                }
+                moved => moved?,
+            }
+        }
+        Ok(())
+    }
+
+    /// The entries of a directory as `ls` prints them, or a file as its own listing
+    fn listing(&self, idx: usize) -> String {
+        let children = match &self.fs.entries[idx] {
+            DirEntry::Dir(dir) => dir.children.as_slice(),
+            DirEntry::File(_) => std::slice::from_ref(&idx),
+        };
+        let mut listing = String::new();
+        for child in children {
+            match &self.fs.entries[*child] {
+                DirEntry::Dir(d) => writeln!(&mut listing, "dir {}", d.name).unwrap(),
+                DirEntry::File(f) => writeln!(&mut listing, "{} {}", f.size, f.name).unwrap(),
+            }
+        }
+        listing.pop();
+        listing
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Command, Conflict, Shell, Warning};
+    use crate::day_7::Filesystem;
+    use crate::examples;
+    use crate::parse::{Line, ParseError};
+
+    #[test]
+    fn test_commands() {
+        let mut shell = Shell::new(Filesystem::new());
+        for command in [
+            "mkdir a",
+            "mkdir /a/b",
+            "cd a/b",
+            "touch f 100",
+            "mkdir ../c",
+            "cd /a/b/../c",
+            "mv ../b/f g",
+            "mv /a/b .",
+            "mkdir d/",
+        ] {
+            assert_eq!(Ok(String::new()), shell.run(command), "{}", command);
+        }
+        assert_eq!(Ok("/a/c".into()), shell.run("pwd"));
+        assert_eq!(Ok("100 g\ndir b\ndir d".into()), shell.run("ls"));
+        assert_eq!(Ok(String::new()), shell.run("cd d"));
+        assert_eq!(Err("/: invalid name".into()), shell.run("mkdir /"));
+        assert_eq!(Ok(String::new()), shell.run("cd .."));
+        for (command, path) in [
+            ("touch g/ 7", "g/"),
+            ("touch h/ 7", "h/"),
+            ("rm g/", "g/"),
+            ("mv g/ h", "g/"),
+            ("mv b g/", "b"),
+            ("ls g/", "g/"),
+        ] {
+            assert_eq!(
+                Err(format!("{}: not a directory", path)),
+                shell.run(command),
+                "{}",
+                command
+            );
+        }
+        assert_eq!(Ok("100 g\ndir b\ndir d".into()), shell.run("ls /a/c/"));
+        assert_eq!(Ok(String::new()), shell.run("rm /a"));
+        assert_eq!(Ok("/".into()), shell.run("pwd"));
+        assert_eq!(Ok(String::new()), shell.run("ls"));
+        assert_eq!(
+            Err("b: no such file or directory".into()),
+            shell.run("cd b")
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut shell = Shell::new(Filesystem::new());
+        shell.replay(&examples::input(7, "example")).unwrap();
+        assert_eq!(Ok("/d".into()), shell.run("pwd"));
+        assert_eq!(Ok("584 i".into()), shell.run("ls /a/e"));
+        assert_eq!(
+            Err(ParseError::new(2, 6, "x/y", "not a directory")),
+            Shell::new(Filesystem::new()).replay("$ touch x 1\n$ cd x/y")
+        );
+        for (listing, name) in [
+            ("dir a/b", "a/b"),
+            ("dir ..", ".."),
+            ("5 .", "."),
+            ("dir x ", "x "),
+        ] {
+            assert_eq!(
+                Err(ParseError::new(
+                    2,
+                    listing.len() - name.len() + 1,
+                    name,
+                    "invalid name"
+                )),
+                Shell::new(Filesystem::new()).replay(&format!("$ ls\n{}", listing))
+            );
+        }
+        assert_eq!(
+            Err(ParseError::new(3, 1, "1 b", "total size too large")),
+            Shell::new(Filesystem::new()).replay("$ ls\n18446744073709551615 a\n1 b")
+        );
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let line = Line {
+            number: 1,
+            text: "mv a",
+        };
+        assert_eq!(
+            Err(ParseError::new(1, 1, "mv a", "usage: mv <from> <to>")),
+            Command::parse(&line, line.text)
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let transcript = "$ cd /\n\
+                          $ ls\n\
+                          dir a\n\
+                          10 b\n\
+                          $ cd ..\n\
+                          $ ls\n\
+                          20 b\n\
+                          dir b\n\
+                          10 a\n\
+                          $ cd x/y";
+        let warning = |line, conflict| Warning { line, conflict };
+        let mut shell = Shell::new(Filesystem::new());
+        shell.enable_validation();
+        shell.replay(transcript).unwrap();
+        assert_eq!(
+            Some(
+                &[
+                    warning(5, Conflict::AboveRoot),
+                    warning(
+                        7,
+                        Conflict::SizeChanged {
+                            path: "/b".into(),
+                            size: 10,
+                            listed: 20
+                        }
+                    ),
+                    warning(8, Conflict::FileListedAsDir("/b".into())),
+                    warning(9, Conflict::DirListedAsFile("/a".into())),
+                    warning(10, Conflict::Unlisted("/x".into())),
+                    warning(10, Conflict::Unlisted("/x/y".into())),
+                ][..]
+            ),
+            shell.warnings()
+        );
+        assert_eq!(Ok("/x/y".into()), shell.run("pwd"));
+        assert_eq!(
+            "line 7: /b was listed with size 10, then with size 20",
+            shell.warnings().unwrap()[1].to_string()
+        );
+
+        assert_eq!(
+            Err(ParseError::new(1, 6, "/b", "not a directory")),
+            shell.replay("$ cd /b")
+        );
+        assert_eq!(Ok("/x/y".into()), shell.run("pwd"));
+        assert_eq!(
+            Err(ParseError::new(5, 6, "..", "already at the root directory")),
+            Shell::new(Filesystem::new()).replay(transcript)
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::{Dir, DirEntry, File, Filesystem};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A terminal transcript that rebuilds the filesystem: `cd` into each directory in turn and
+/// `ls` its entries in the order they were listed
+pub fn transcript(fs: &Filesystem) -> String {
+    let mut transcript = String::from("$ cd /\n");
+    write_dir(fs, 0, &mut transcript);
+    transcript
+}
+
+fn write_dir(fs: &Filesystem, idx: usize, transcript: &mut String) {
+    let Some(dir) = fs.entries[idx].dir() else {
+        return;
+    };
+    writeln!(transcript, "$ ls").unwrap();
+    for &child in &dir.children {
+        match &fs.entries[child] {
+            DirEntry::Dir(d) => writeln!(transcript, "dir {}", d.name).unwrap(),
+            DirEntry::File(f) => writeln!(transcript, "{} {}", f.size, f.name).unwrap(),
+        }
+    }
+    for &child in &dir.children {
+        if let DirEntry::Dir(d) = &fs.entries[child] {
+            writeln!(transcript, "$ cd {}", d.name).unwrap();
+            write_dir(fs, child, transcript);
+            writeln!(transcript, "$ cd ..").unwrap();
+        }
+    }
+}
+
+/// Reads a directory on disk into a filesystem, with its entries sorted by name. Anything
+/// that is neither a file nor a directory, such as a symbolic link, is left out. Fails on a
+/// name a transcript cannot carry, like one that is not UTF-8 or ends in a space.
+pub fn from_dir(path: &Path) -> io::Result<Filesystem> {
+    let mut fs = Filesystem::new();
+    read_dir(&mut fs, 0, path)?;
+    Ok(fs)
+}
+
+fn read_dir(fs: &mut Filesystem, idx: usize, path: &Path) -> io::Result<()> {
+    let mut entries = fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = entry
+            .file_name()
+            .into_string()
+            .ok()
+            .filter(|name| Filesystem::is_valid_name(name) && fs.child(idx, name).is_none())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{}: name cannot be written to a transcript",
+                        entry.path().display()
+                    ),
+                )
+            })?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        let too_large = |e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", entry.path().display(), e),
+            )
+        };
+        if metadata.is_dir() {
+            let child = fs.entries.len();
+            fs.insert(idx, DirEntry::Dir(Dir::new(&name)))
+                .map_err(too_large)?;
+            read_dir(fs, child, &entry.path())?;
+        } else if metadata.is_file() {
+            fs.insert(
+                idx,
+                DirEntry::File(File::new(&name, metadata.len() as usize)),
+            )
+            .map_err(too_large)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_dir, transcript};
+    use crate::day_7::Filesystem;
+    use crate::examples;
+    use std::fs;
+
+    #[test]
+    fn test_transcript() {
+        let fs = Filesystem::init(&examples::input(7, "example")).unwrap();
+        let transcript = transcript(&fs);
+        assert!(transcript.starts_with("$ cd /\n$ ls\ndir a\n14848514 b.txt\n"));
+        assert_eq!(
+            transcript,
+            super::transcript(&Filesystem::init(&transcript).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_from_dir() {
+        let dir = std::env::temp_dir().join(format!("day_7_from_dir_{}", std::process::id()));
+        fs::create_dir_all(dir.join("a b")).unwrap();
+        fs::write(dir.join("a b/c.txt"), "hello").unwrap();
+        fs::write(dir.join("d"), "").unwrap();
+        fs::create_dir_all(dir.join("é $ ls")).unwrap();
+        let read = from_dir(&dir);
+        fs::create_dir_all(dir.join("x ")).unwrap();
+        let trailing_space = from_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let expected = "$ cd /\n$ ls\ndir a b\n0 d\ndir é $ ls\n\
+                        $ cd a b\n$ ls\n5 c.txt\n$ cd ..\n$ cd é $ ls\n$ ls\n$ cd ..\n";
+        let read = read.unwrap();
+        assert_eq!(expected, transcript(&read));
+        let replayed = Filesystem::init(expected).unwrap();
+        assert_eq!(read.tree(None, false), replayed.tree(None, false));
+        let Err(e) = trailing_space else {
+            panic!("expected an error for a name ending in a space");
+        };
+        assert!(e
+            .to_string()
+            .ends_with("x : name cannot be written to a transcript"));
+    }
+}
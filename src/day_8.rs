@@ -0,0 +1,73 @@
+use crate::grid::{Direction, Grid};
+use crate::parse::ParseError;
+use crate::Solution;
+use itertools::FoldWhile::{Continue, Done};
+use itertools::Itertools;
+
+/// The heights of the trees in a forest
+pub struct Forest(Grid<u32>);
+
+impl Forest {
+    pub fn visible_from_outside(&self) -> usize {
+        self.0
+            .coords()
+            .filter(|&pos| {
+                let height = self.0[pos];
+                Direction::ALL
+                    .into_iter()
+                    .any(|d| self.0.ray_values(pos, d).all(|other| *other < height))
+            })
+            .count()
+    }
+
+    pub fn score(&self) -> usize {
+        self.0
+            .coords()
+            .filter(|&pos| !self.0.is_edge(pos))
+            .map(|pos| {
+                let height = self.0[pos];
+                Direction::ALL
+                    .map(|d| {
+                        self.0
+                            .ray_values(pos, d)
+                            .fold_while(0, |acc, other| {
+                                if height <= *other {
+                                    Done(acc + 1)
+                                } else {
+                                    Continue(acc + 1)
+                                }
+                            })
+                            .into_inner()
+                    })
+                    .iter()
+                    .product()
+            })
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl Solution for Forest {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10)).map(Self)
+    }
+
+    fn part_1(&self) -> usize {
+        self.visible_from_outside()
+    }
+
+    fn part_2(&self) -> usize {
+        self.score()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Forest;
+    use crate::examples::examples;
+
+    examples!(Forest, 8);
+}
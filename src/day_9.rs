@@ -0,0 +1,127 @@
+use crate::grid::Direction;
+use crate::parse::{self, Line, ParseError};
+use crate::Solution;
+use std::collections::HashSet;
+
+pub struct Motion {
+    direction: Direction,
+    steps: usize,
+}
+
+impl Motion {
+    pub fn parse(line: &Line) -> Result<Self, ParseError> {
+        let (direction, steps) = line
+            .text
+            .split_once(' ')
+            .ok_or_else(|| line.error(line.text, "expected a motion"))?;
+        let direction = match direction {
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            _ => return Err(line.error(direction, "unknown direction")),
+        };
+        Ok(Self {
+            direction,
+            steps: line.parse(steps)?,
+        })
+    }
+}
+
+/// A rope of knots, where each knot follows the one in front of it
+pub struct Rope {
+    knots: Vec<(i32, i32)>,
+    visited: HashSet<(i32, i32)>,
+}
+
+impl Rope {
+    pub fn new(knots: usize) -> Self {
+        assert!(knots > 0, "rope must have at least one knot");
+        Self {
+            knots: vec![(0, 0); knots],
+            visited: HashSet::from([(0, 0)]),
+        }
+    }
+
+    pub fn apply(&mut self, motion: &Motion) {
+        for _ in 0..motion.steps {
+            self.step(motion.direction);
+        }
+    }
+
+    fn step(&mut self, direction: Direction) {
+        let (dx, dy) = direction.delta();
+        let head = &mut self.knots[0];
+        head.0 += dx as i32;
+        head.1 += dy as i32;
+
+        for i in 1..self.knots.len() {
+            let (x, y) = self.knots[i - 1];
+            let knot = &mut self.knots[i];
+            let (dx, dy) = (x - knot.0, y - knot.1);
+            if dx.abs() <= 1 && dy.abs() <= 1 {
+                break;
+            }
+            knot.0 += dx.signum();
+            knot.1 += dy.signum();
+        }
+
+        self.visited.insert(*self.knots.last().unwrap());
+    }
+
+    pub fn tail(&self) -> (i32, i32) {
+        *self.knots.last().unwrap()
+    }
+
+    /// The number of distinct positions the tail has visited
+    pub fn tail_visited(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+pub struct Motions(Vec<Motion>);
+
+impl Motions {
+    pub fn simulate(&self, knots: usize) -> Rope {
+        let mut rope = Rope::new(knots);
+        for motion in &self.0 {
+            rope.apply(motion);
+        }
+        rope
+    }
+}
+
+impl Solution for Motions {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse::lines(input)
+            .map(|line| Motion::parse(&line))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn part_1(&self) -> usize {
+        self.simulate(2).tail_visited()
+    }
+
+    fn part_2(&self) -> usize {
+        self.simulate(10).tail_visited()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Motions;
+    use crate::examples::{self, examples};
+    use crate::Solution;
+
+    examples!(Motions, 9);
+
+    #[test]
+    fn test_tail() {
+        let motions = Motions::parse(&examples::input(9, "example")).unwrap();
+        assert_eq!((1, -2), motions.simulate(2).tail());
+    }
+}
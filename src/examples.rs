@@ -0,0 +1,80 @@
+use crate::answers::{Answers, Verdict};
+use crate::runner::Part;
+use crate::Solution;
+use std::fs;
+use std::path::PathBuf;
+
+/// Generates `test_part_1` and `test_part_2`, checking a solution against every example
+/// in `fixtures/day_<n>` that has an expected answer for the part
+macro_rules! examples {
+    ($solution:ty, $day:expr) => {
+        #[test]
+        fn test_part_1() {
+            crate::examples::check::<$solution>($day, crate::runner::Part::One);
+        }
+
+        #[test]
+        fn test_part_2() {
+            crate::examples::check::<$solution>($day, crate::runner::Part::Two);
+        }
+    };
+}
+
+pub(crate) use examples;
+
+fn dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("day_{}", day))
+}
+
+/// The input of a day's example, from `fixtures/day_<n>/<name>.txt`
+pub fn input(day: u8, name: &str) -> String {
+    let path = dir(day).join(format!("{}.txt", name));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Checks a part against each of a day's examples with an expected answer in
+/// `fixtures/day_<n>/<name>.toml`
+pub fn check<S: Solution>(day: u8, part: Part) {
+    let mut names = fs::read_dir(dir(day))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+
+    let mut checked = 0;
+    for name in names {
+        let path = dir(day).join(format!("{}.toml", name));
+        let answers = match Answers::read(&path).unwrap() {
+            Some(answers) if answers.expected(part).is_some() => answers,
+            _ => continue,
+        };
+
+        let solution = S::parse(&input(day, &name)).unwrap();
+        let answer = match part {
+            Part::One => solution.part_1().to_string(),
+            Part::Two => solution.part_2().to_string(),
+        };
+        if let Verdict::Fail { expected } = answers.verify(part, &answer) {
+            panic!(
+                "day {} example {:?} part {}: expected {:?}, found {:?}",
+                day,
+                name,
+                part.number(),
+                expected,
+                answer
+            );
+        }
+        checked += 1;
+    }
+
+    assert!(
+        checked > 0,
+        "day {} has no examples with a part {} answer",
+        day,
+        part.number()
+    );
+}
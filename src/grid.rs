@@ -0,0 +1,274 @@
+use crate::parse::{self, ParseError};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ];
+
+    /// The change in `(x, y)` of a step in this direction, where `y` increases downwards
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+        }
+    }
+}
+
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// A rectangular grid of cells, addressed by `(x, y)` with `(0, 0)` in the top left
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a map of characters, one row per line, converting each character to a cell
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in parse::lines(input) {
+            let len = line.text.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(line.error(line.text, format!("expected {} columns", w)))
+                }
+                Some(_) => {}
+            }
+            for (i, c) in line.text.char_indices() {
+                cells.push(cell(c).ok_or_else(|| {
+                    line.error(&line.text[i..i + c.len_utf8()], "unexpected character")
+                })?);
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Whether a cell is on the outer edge of the grid
+    pub fn is_edge(&self, (x, y): (usize, usize)) -> bool {
+        x == 0 || y == 0 || x + 1 == self.width || y + 1 == self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(pos.1 * self.width + pos.0)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.cells.get_mut(pos.1 * self.width + pos.0)
+        } else {
+            None
+        }
+    }
+
+    /// All coordinates, row by row
+    pub fn coords(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        if x < self.width {
+            Some(self.cells.iter().skip(x).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    /// The coordinate one step away in a direction, if it is within the grid
+    pub fn step(&self, pos: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(pos, direction.delta())
+    }
+
+    fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The coordinates of the 4-connected neighbors of a cell
+    pub fn neighbors(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The coordinates of the 8-connected neighbors of a cell, including diagonals
+    pub fn neighbors_diagonal(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(pos).chain(
+            DIAGONALS
+                .into_iter()
+                .filter_map(move |delta| self.offset(pos, delta)),
+        )
+    }
+
+    /// The coordinates from a cell to the edge of the grid in a direction, excluding the cell
+    pub fn ray(
+        &self,
+        pos: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.step(pos, direction), move |&p| self.step(p, direction))
+    }
+
+    /// The cells from a cell to the edge of the grid in a direction, excluding the cell
+    pub fn ray_values(
+        &self,
+        pos: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = &T> + '_ {
+        self.ray(pos, direction).map(move |p| &self[p])
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Direction, Grid};
+    use crate::parse::ParseError;
+
+    const TEST_INPUT: &str = r#"123
+456
+789"#;
+
+    fn grid() -> Grid<u32> {
+        Grid::parse(TEST_INPUT, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(Some(&6), grid.get((2, 1)));
+        assert_eq!(None, grid.get((3, 1)));
+        assert_eq!(format!("{}\n", TEST_INPUT), grid.to_string());
+        assert_eq!(
+            Err(ParseError::new(2, 2, "x", "unexpected character")),
+            Grid::parse("12\n3x", |c| c.to_digit(10))
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 1, "3", "expected 2 columns")),
+            Grid::parse("12\n3", |c| c.to_digit(10))
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+        assert_eq!(Some(&[4, 5, 6][..]), grid.row(1));
+        assert_eq!(
+            vec![&2, &5, &8],
+            grid.column(1).unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&5, &2],
+            grid.ray_values((1, 2), Direction::Up).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 1), (2, 1)],
+            grid.ray((0, 1), Direction::Right).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(2, grid.neighbors((0, 0)).count());
+        assert_eq!(4, grid.neighbors((1, 1)).count());
+        assert_eq!(3, grid.neighbors_diagonal((0, 0)).count());
+        assert_eq!(8, grid.neighbors_diagonal((1, 1)).count());
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The default directory from which puzzle inputs are read
+pub const INPUTS_DIR: &str = "input";
+
+/// Where to read a day's puzzle input from
+pub enum Source {
+    /// A specific file
+    Path(PathBuf),
+    /// Standard input
+    Stdin,
+    /// A directory containing `day_<n>.txt` files
+    Dir(PathBuf),
+    /// Inputs embedded into the binary at compile time
+    #[cfg(feature = "embed-inputs")]
+    Embedded,
+}
+
+impl Default for Source {
+    #[cfg(feature = "embed-inputs")]
+    fn default() -> Self {
+        Source::Embedded
+    }
+
+    #[cfg(not(feature = "embed-inputs"))]
+    fn default() -> Self {
+        Source::Dir(INPUTS_DIR.into())
+    }
+}
+
+impl Source {
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            Source::Path(path) => read(day, path),
+            Source::Stdin => io::read_to_string(io::stdin()).map_err(|source| InputError::Io {
+                path: "<stdin>".into(),
+                source,
+            }),
+            Source::Dir(dir) => read(day, &dir.join(format!("day_{}.txt", day))),
+            #[cfg(feature = "embed-inputs")]
+            Source::Embedded => embedded(day)
+                .map(String::from)
+                .ok_or(InputError::NotEmbedded { day }),
+        }
+    }
+}
+
+fn read(day: u8, path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound {
+            day,
+            path: path.to_path_buf(),
+        },
+        _ => InputError::Io {
+            path: path.to_path_buf(),
+            source,
+        },
+    })
+}
+
+#[cfg(feature = "embed-inputs")]
+fn embedded(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("../input/day_1.txt")),
+        2 => Some(include_str!("../input/day_2.txt")),
+        3 => Some(include_str!("../input/day_3.txt")),
+        4 => Some(include_str!("../input/day_4.txt")),
+        5 => Some(include_str!("../input/day_5.txt")),
+        6 => Some(include_str!("../input/day_6.txt")),
+        7 => Some(include_str!("../input/day_7.txt")),
+        8 => Some(include_str!("../input/day_8.txt")),
+        9 => Some(include_str!("../input/day_9.txt")),
+        10 => Some(include_str!("../input/day_10.txt")),
+        11 => Some(include_str!("../input/day_11.txt")),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        day: u8,
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[cfg(feature = "embed-inputs")]
+    NotEmbedded {
+        day: u8,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { day, path } => write!(
+                f,
+                "input for day {} not found at {}. Download it from \
+                 https://adventofcode.com/2022/day/{}/input or pass --input <PATH>",
+                day,
+                path.display(),
+                day
+            ),
+            InputError::Io { path, source } => {
+                write!(f, "could not read input {}: {}", path.display(), source)
+            }
+            #[cfg(feature = "embed-inputs")]
+            InputError::NotEmbedded { day } => {
+                write!(f, "input for day {} is not embedded in this build", day)
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InputError, Source};
+
+    #[test]
+    fn test_missing_input() {
+        let source = Source::Dir("does_not_exist".into());
+        match source.load(7) {
+            Err(InputError::NotFound { day, path }) => {
+                assert_eq!(7, day);
+                assert_eq!("does_not_exist/day_7.txt", path.to_str().unwrap());
+            }
+            _ => panic!("expected input not found"),
+        }
+    }
+}
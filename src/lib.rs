@@ -0,0 +1,35 @@
+use crate::parse::ParseError;
+use std::fmt::Display;
+
+pub mod answers;
+pub mod bench;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+#[cfg(test)]
+mod examples;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod runner;
+
+/// A solution to a day's puzzle, parsed from the puzzle input
+pub trait Solution: Sized {
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    fn part_1(&self) -> Self::Part1;
+
+    fn part_2(&self) -> Self::Part2;
+}
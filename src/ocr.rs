@@ -0,0 +1,122 @@
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// The width of a glyph and the blank column separating it from the next
+const GLYPH_SPACING: usize = GLYPH_WIDTH + 1;
+
+/// The 4x6 font drawn on the CRT, each glyph's rows concatenated
+const FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum OcrError {
+    Parse(ParseError),
+    Height(usize),
+    Unrecognized { index: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Parse(e) => write!(f, "invalid screen: {}", e),
+            OcrError::Height(height) => write!(
+                f,
+                "expected a screen {} rows high but found {}",
+                GLYPH_HEIGHT, height
+            ),
+            OcrError::Unrecognized { index, glyph } => {
+                write!(f, "unrecognized glyph at letter {}:\n{}", index + 1, glyph)
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Reads the letters drawn on a screen of `#` and `.` pixels
+pub fn recognize(screen: &str) -> Result<String, OcrError> {
+    let pixels = Grid::parse(screen.trim_end(), |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .map_err(OcrError::Parse)?;
+
+    if pixels.height() != GLYPH_HEIGHT {
+        return Err(OcrError::Height(pixels.height()));
+    }
+
+    (0..(pixels.width() + 1) / GLYPH_SPACING)
+        .map(|index| {
+            let glyph = (0..GLYPH_HEIGHT)
+                .flat_map(|y| (0..GLYPH_WIDTH).map(move |x| (index * GLYPH_SPACING + x, y)))
+                .map(|pos| if pixels[pos] { '#' } else { '.' })
+                .collect::<String>();
+
+            FONT.iter()
+                .find(|(_, g)| *g == glyph)
+                .map(|(c, _)| *c)
+                .ok_or_else(|| OcrError::Unrecognized {
+                    index,
+                    glyph: glyph
+                        .as_bytes()
+                        .chunks(GLYPH_WIDTH)
+                        .map(|row| String::from_utf8_lossy(row))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{recognize, OcrError};
+
+    const TEST_INPUT: &str = r#"####..##..###...##....##.####...##.####.
+...#.#..#.#..#.#..#....#.#.......#....#.
+..#..#....###..#..#....#.###.....#...#..
+.#...#....#..#.####....#.#.......#..#...
+#....#..#.#..#.#..#.#..#.#....#..#.#....
+####..##..###..#..#..##..#.....##..####.
+"#;
+
+    #[test]
+    fn test_recognize() {
+        assert_eq!(Ok("ZCBAJFJZ".to_string()), recognize(TEST_INPUT));
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        let screen = TEST_INPUT.replacen("####..##", "####..#.", 1);
+        assert_eq!(
+            Err(OcrError::Unrecognized {
+                index: 1,
+                glyph: ".#..\n#..#\n#...\n#...\n#..#\n.##.".into()
+            }),
+            recognize(&screen)
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error parsing puzzle input, pointing at the offending text
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    text: String,
+    reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, reason: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// The 1-based line number of the offending text
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column of the offending text
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} {:?}",
+            self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input with its 1-based line number
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error for a span of this line. The column is found from the span's position
+    /// within the line, or is the start of the line if the span is not a slice of it.
+    pub fn error(&self, span: &str, reason: impl Into<String>) -> ParseError {
+        let base = self.text.as_ptr() as usize;
+        let start = span.as_ptr() as usize;
+        let column = if start >= base && start + span.len() <= base + self.text.len() {
+            self.text[..start - base].chars().count() + 1
+        } else {
+            1
+        };
+        ParseError::new(self.number, column, span, reason)
+    }
+
+    /// An error for something expected but missing at the end of this line
+    pub fn missing(&self, what: &str) -> ParseError {
+        ParseError::new(
+            self.number,
+            self.text.chars().count() + 1,
+            "",
+            format!("expected {}", what),
+        )
+    }
+
+    /// Parses a span of this line
+    pub fn parse<T>(&self, span: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        span.parse()
+            .map_err(|e: T::Err| self.error(span, e.to_string()))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::lines;
+
+    #[test]
+    fn test_error_column() {
+        let line = lines("abc\nmove x from 1").nth(1).unwrap();
+        let err = line.parse::<usize>(&line.text[5..6]).unwrap_err();
+        assert_eq!(2, err.line());
+        assert_eq!(6, err.column());
+        assert_eq!("x", err.text());
+        assert_eq!(
+            "line 2, column 6: invalid digit found in string \"x\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        let line = lines("A").next().unwrap();
+        let err = line.missing("a second column");
+        assert_eq!(2, err.column());
+        assert_eq!("expected a second column", err.reason());
+    }
+}
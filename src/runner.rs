@@ -0,0 +1,115 @@
+use crate::bench::{self, Samples};
+use crate::day_1::Inventories;
+use crate::day_10::Device;
+use crate::day_11::Monkeys;
+use crate::day_2::StrategyGuide;
+use crate::day_3::Rucksacks;
+use crate::day_4::Pairs;
+use crate::day_5::Procedure;
+use crate::day_6::DataStream;
+use crate::day_7::Filesystem;
+use crate::day_8::Forest;
+use crate::day_9::Motions;
+use crate::parse::ParseError;
+use crate::Solution;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The answer to a part, along with how long it took to solve
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// The result of running a day against an input
+pub struct Run {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// A registered day's puzzle
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    run: fn(&str, &[Part]) -> Result<Run, ParseError>,
+    bench: fn(&str, usize) -> Result<Samples, ParseError>,
+}
+
+impl Day {
+    const fn new<S: Solution>(day: u8, title: &'static str) -> Self {
+        Self {
+            day,
+            title,
+            run: run::<S>,
+            bench: bench::samples::<S>,
+        }
+    }
+
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+        (self.run)(input, parts)
+    }
+
+    /// Times parsing and solving each part over a number of iterations
+    pub fn bench(&self, input: &str, iterations: usize) -> Result<Samples, ParseError> {
+        (self.bench)(input, iterations)
+    }
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let solution = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => solution.part_1().to_string(),
+                Part::Two => solution.part_2().to_string(),
+            };
+            Answer {
+                part: *part,
+                value,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Run { parse, answers })
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<Inventories>(1, "Calorie Counting"),
+    Day::new::<StrategyGuide>(2, "Rock Paper Scissors"),
+    Day::new::<Rucksacks>(3, "Rucksack Reorganization"),
+    Day::new::<Pairs>(4, "Camp Cleanup"),
+    Day::new::<Procedure>(5, "Supply Stacks"),
+    Day::new::<DataStream>(6, "Tuning Trouble"),
+    Day::new::<Filesystem>(7, "No Space Left On Device"),
+    Day::new::<Forest>(8, "Treetop Tree House"),
+    Day::new::<Motions>(9, "Rope Bridge"),
+    Day::new::<Device>(10, "Cathode-Ray Tube"),
+    Day::new::<Monkeys>(11, "Monkey in the Middle"),
+];
+
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}